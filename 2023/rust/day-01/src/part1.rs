@@ -12,13 +12,13 @@ fn recover_calibration_value(line: &str) -> u32 {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let answer = input
         .lines()
         .map(recover_calibration_value)
         .sum::<u32>();
 
-    Ok(answer.to_string())
+    Ok(answer)
 }
 
 #[cfg(test)]
@@ -32,7 +32,7 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
 ";
-        assert_eq!("142", process(input)?);
+        assert_eq!(142, process(input)?);
         Ok(())
     }
 }
@@ -47,13 +47,13 @@ fn recover_calibration_value(line: &str) -> u32 {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let answer = input
         .lines()
         .map(recover_calibration_value)
         .sum::<u32>();
 
-    Ok(answer.to_string())
+    Ok(answer)
 }
 
 #[cfg(test)]
@@ -63,16 +63,16 @@ mod tests {
     use super::*;
 
     #[rstest]
-    #[case("two1nine", "29")]
-    #[case("eightwothree", "83")]
-    #[case("abcone2threexyz", "13")]
-    #[case("xtwone3four", "24")]
-    #[case("4nineeightseven2", "42")]
-    #[case("zoneight234", "14")]
-    #[case("7pqrstsixteen", "76")]
+    #[case("two1nine", 29)]
+    #[case("eightwothree", 83)]
+    #[case("abcone2threexyz", 13)]
+    #[case("xtwone3four", 24)]
+    #[case("4nineeightseven2", 42)]
+    #[case("zoneight234", 14)]
+    #[case("7pqrstsixteen", 76)]
     fn test_process(
         #[case] input: &str,
-        #[case] expected: &str,
+        #[case] expected: u32,
     ) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
@@ -0,0 +1,29 @@
+use aoc_2023_day_02::*;
+use criterion::{
+    Criterion, criterion_group, criterion_main,
+};
+
+fn part1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_02::part1");
+    let input = aoc_core::input::for_bench(2023, 2);
+    group.bench_with_input(
+        "part1",
+        input.as_str(),
+        |b, input| b.iter(|| part1::process(input)),
+    );
+    group.finish();
+}
+
+fn part2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_02::part2");
+    let input = aoc_core::input::for_bench(2023, 2);
+    group.bench_with_input(
+        "part2",
+        input.as_str(),
+        |b, input| b.iter(|| part2::process(input)),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2);
+criterion_main!(benches);
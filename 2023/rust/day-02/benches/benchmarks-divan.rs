@@ -0,0 +1,21 @@
+use aoc_2023_day_02::*;
+
+fn main() {
+    divan::main();
+}
+
+#[divan::bench]
+fn part1(bencher: divan::Bencher) {
+    let input = aoc_core::input::for_bench(2023, 2);
+    bencher.bench(|| {
+        part1::process(divan::black_box(&input)).unwrap()
+    });
+}
+
+#[divan::bench]
+fn part2(bencher: divan::Bencher) {
+    let input = aoc_core::input::for_bench(2023, 2);
+    bencher.bench(|| {
+        part2::process(divan::black_box(&input)).unwrap()
+    });
+}
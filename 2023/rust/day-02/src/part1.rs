@@ -1,16 +1,4 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
-#[derive(Error, Debug, Diagnostic)]
-pub enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-
-    #[error("Parse error: `{0}`")]
-    #[diagnostic(code(aoc::parser_error))]
-    ParseError(String),
-}
+use aoc_core::AocError;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Color {
@@ -51,33 +39,33 @@ struct Bag {
 }
 
 mod parser {
+    use aoc_core::parse::{
+        IResult, each_line, id_prefix, tag,
+    };
     use nom::{
-        IResult, Parser,
+        Parser,
         branch::alt,
-        bytes::complete::tag,
         character::complete::{space0, u32},
         combinator::{map_res, value},
+        error::context,
         multi::separated_list1,
-        sequence::{delimited, pair, preceded},
+        sequence::{pair, preceded},
     };
 
     use super::*;
 
-    fn game(input: &str) -> IResult<&str, Game, ()> {
+    fn game(input: &str) -> IResult<'_, Game> {
         // Game 13: 7 blue, 8 red; 5 green, 15 blue, 2
         // red; 7 green, 3 blue, 12 red
 
-        let color = alt((
-            value(Color::Red, tag::<&str, &str, ()>("red")),
-            value(
-                Color::Green,
-                tag::<&str, &str, ()>("green"),
-            ),
-            value(
-                Color::Blue,
-                tag::<&str, &str, ()>("blue"),
-            ),
-        ));
+        let color = context(
+            "cube color",
+            alt((
+                value(Color::Red, tag("red")),
+                value(Color::Green, tag("green")),
+                value(Color::Blue, tag("blue")),
+            )),
+        );
 
         let colored_cubes =
             pair(u32, preceded(space0, color));
@@ -85,8 +73,7 @@ mod parser {
             separated_list1(tag(", "), colored_cubes);
         let game_round =
             separated_list1(tag("; "), colored_cubes_list);
-        let game_id =
-            delimited(tag("Game "), u32, tag(": "));
+        let game_id = context("game id", id_prefix("Game"));
         let game = pair(game_id, game_round);
 
         let mut parser = map_res(game, |(id, rounds)| {
@@ -96,27 +83,22 @@ mod parser {
         parser.parse(input)
     }
 
-    pub fn parse_game(
+    pub fn parse_games(
         input: &str,
-    ) -> Result<Game, AocError> {
-        game(input).map(|x| x.1).map_err(|_| {
-            AocError::ParseError(input.to_string())
-        })
+    ) -> Result<Vec<Game>, AocError> {
+        each_line(input, game)
     }
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let bag = Bag {
         red: 12u32,
         green: 13u32,
         blue: 14u32,
     };
 
-    let games = input
-        .lines()
-        .map(parser::parse_game)
-        .collect::<Result<Vec<Game>, _>>()?;
+    let games = parser::parse_games(input)?;
 
     let sum = games
         .into_iter()
@@ -124,7 +106,7 @@ pub fn process(input: &str) -> miette::Result<String> {
         .map(|game| game.id)
         .sum::<u32>();
 
-    Ok(sum.to_string())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -150,8 +132,8 @@ mod tests {
                 ],
             ],
         );
-        let game = parser::parse_game(input)?;
-        assert_eq!(game, expected);
+        let games = parser::parse_games(input)?;
+        assert_eq!(games, vec![expected]);
 
         Ok(())
     }
@@ -164,7 +146,7 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        assert_eq!("8", process(input)?);
+        assert_eq!(8, process(input)?);
         Ok(())
     }
 }
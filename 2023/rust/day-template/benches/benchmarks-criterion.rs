@@ -1,12 +1,12 @@
 use criterion::{
     Criterion, criterion_group, criterion_main,
 };
-use {{crate_name}}::*;
+use aoc_2023_{{crate_name}}::*;
 
 fn part1(c: &mut Criterion) {
     let mut group = c.benchmark_group("{{crate_name}}::part1");
-    let input = include_str!("../input1.txt");
-    group.bench_with_input("part1", input, |b, input| {
+    let input = aoc_core::input::for_bench(2023, {{project-name | remove: "day-" | plus: 0}});
+    group.bench_with_input("part1", input.as_str(), |b, input| {
         b.iter(|| part1::process(input))
     });
     group.finish();
@@ -14,8 +14,8 @@ fn part1(c: &mut Criterion) {
 
 fn part2(c: &mut Criterion) {
     let mut group = c.benchmark_group("{{crate_name}}::part2");
-    let input = include_str!("../input2.txt");
-    group.bench_with_input("part2", input, |b, input| {
+    let input = aoc_core::input::for_bench(2023, {{project-name | remove: "day-" | plus: 0}});
+    group.bench_with_input("part2", input.as_str(), |b, input| {
         b.iter(|| part2::process(input))
     });
     group.finish();
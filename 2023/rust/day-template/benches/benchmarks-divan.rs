@@ -1,17 +1,21 @@
-use {{crate_name}}::*;
+use aoc_2023_{{crate_name}}::*;
 
 fn main() {
     divan::main();
 }
 
 #[divan::bench]
-fn part1() {
-    let input = include_str!("../input1.txt");
-    part1::process(divan::black_box(input)).unwrap();
+fn part1(bencher: divan::Bencher) {
+    let input = aoc_core::input::for_bench(2023, {{project-name | remove: "day-" | plus: 0}});
+    bencher.bench(|| {
+        part1::process(divan::black_box(&input)).unwrap()
+    });
 }
 
 #[divan::bench]
-fn part2() {
-    let input = include_str!("../input2.txt");
-    part2::process(divan::black_box(input)).unwrap();
+fn part2(bencher: divan::Bencher) {
+    let input = aoc_core::input::for_bench(2023, {{project-name | remove: "day-" | plus: 0}});
+    bencher.bench(|| {
+        part2::process(divan::black_box(&input)).unwrap()
+    });
 }
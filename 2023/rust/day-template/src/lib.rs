@@ -1,2 +1,12 @@
 pub mod part1;
 pub mod part2;
+
+aoc_core::register! {
+    year: 2023,
+    day: {{project-name | remove: "day-" | plus: 0}},
+    part1: part1::process,
+    part2: part2::process,
+}
+
+#[cfg(test)]
+aoc_test::examples!();
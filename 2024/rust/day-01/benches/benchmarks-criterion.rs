@@ -1,23 +1,27 @@
+use aoc_2024_day_01::*;
 use criterion::{
     Criterion, criterion_group, criterion_main,
 };
-use day_01::*;
 
 fn part1(c: &mut Criterion) {
     let mut group = c.benchmark_group("day_01::part1");
-    let input = include_str!("../input1.txt");
-    group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
-    });
+    let input = aoc_core::input::for_bench(2024, 1);
+    group.bench_with_input(
+        "part1",
+        input.as_str(),
+        |b, input| b.iter(|| part1::process(input)),
+    );
     group.finish();
 }
 
 fn part2(c: &mut Criterion) {
     let mut group = c.benchmark_group("day_01::part2");
-    let input = include_str!("../input2.txt");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
-    });
+    let input = aoc_core::input::for_bench(2024, 1);
+    group.bench_with_input(
+        "part2",
+        input.as_str(),
+        |b, input| b.iter(|| part2::process(input)),
+    );
     group.finish();
 }
 
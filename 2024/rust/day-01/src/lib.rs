@@ -1,2 +1,12 @@
 pub mod part1;
 pub mod part2;
+
+aoc_core::register! {
+    year: 2024,
+    day: 1,
+    part1: part1::process,
+    part2: part2::process,
+}
+
+#[cfg(test)]
+aoc_test::examples!();
@@ -1,46 +1,34 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
-#[derive(Error, Debug, Diagnostic)]
-pub enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-
-    #[error("Parse error: `{0}`")]
-    #[diagnostic(code(aoc::parser_error))]
-    ParseError(String),
-}
+use aoc_core::AocError;
 
 mod parser {
+    use aoc_core::parse::{IResult, each_line};
     use nom::{
-        IResult, Parser,
+        Parser,
         character::complete::{space1, u32},
+        error::context,
         sequence::separated_pair,
     };
 
     use super::*;
 
-    fn tuple(input: &str) -> IResult<&str, (u32, u32), ()> {
-        let mut parser = separated_pair(u32, space1, u32);
+    fn tuple(input: &str) -> IResult<'_, (u32, u32)> {
+        let mut parser = context(
+            "location ids",
+            separated_pair(u32, space1, u32),
+        );
         parser.parse(input)
     }
 
     pub fn parse(
         input: &str,
-    ) -> Result<(u32, u32), AocError> {
-        tuple(input).map(|x| x.1).map_err(|_| {
-            AocError::ParseError(input.to_string())
-        })
+    ) -> Result<Vec<(u32, u32)>, AocError> {
+        each_line(input, tuple)
     }
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let tuples = input
-        .lines()
-        .map(parser::parse)
-        .collect::<Result<Vec<(u32, u32)>, _>>()?;
+pub fn process(input: &str) -> miette::Result<u32> {
+    let tuples = parser::parse(input)?;
 
     let (mut ids0, mut ids1): (Vec<u32>, Vec<u32>) =
         tuples.iter().fold(
@@ -60,7 +48,7 @@ pub fn process(input: &str) -> miette::Result<String> {
         .zip(ids1)
         .fold(0u32, |acc, (a, b)| acc + a.abs_diff(b));
 
-    Ok(sum.to_string())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -70,7 +58,7 @@ mod tests {
     #[test]
     fn test_parser() -> miette::Result<()> {
         let input = "143   142";
-        let expected = (143, 142);
+        let expected = vec![(143, 142)];
         let actual = parser::parse(input)?;
         assert_eq!(expected, actual);
         Ok(())
@@ -84,7 +72,7 @@ mod tests {
 1   3
 3   9
 3   3";
-        assert_eq!("11", process(input)?);
+        assert_eq!(11, process(input)?);
 
         Ok(())
     }
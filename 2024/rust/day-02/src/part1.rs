@@ -1,36 +1,20 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
-#[derive(Error, Debug, Diagnostic)]
-pub enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-
-    #[error("Parse error: `{0}`")]
-    #[diagnostic(code(aoc::parser_error))]
-    ParseError(String),
-}
+use aoc_core::AocError;
 
 mod parser {
-    use nom::{
-        IResult, Parser, bytes::complete::tag,
-        character::complete::u32, multi::separated_list1,
-    };
+    use aoc_core::parse::{IResult, each_line, numbers};
+    use nom::{Parser, error::context};
 
     use super::*;
 
-    fn report(input: &str) -> IResult<&str, Vec<u32>, ()> {
-        let mut parser = separated_list1(tag(" "), u32);
+    fn report(input: &str) -> IResult<'_, Vec<u32>> {
+        let mut parser = context("report", numbers);
         parser.parse(input)
     }
 
     pub fn parse(
         input: &str,
-    ) -> Result<Vec<u32>, AocError> {
-        report(input).map(|x| x.1).map_err(|_| {
-            AocError::ParseError(input.to_string())
-        })
+    ) -> Result<Vec<Vec<u32>>, AocError> {
+        each_line(input, report)
     }
 }
 
@@ -52,16 +36,13 @@ fn is_safe(xs: &[u32]) -> bool {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let reports = input
-        .lines()
-        .map(parser::parse)
-        .collect::<Result<Vec<Vec<u32>>, _>>()?;
+pub fn process(input: &str) -> miette::Result<usize> {
+    let reports = parser::parse(input)?;
 
     let sum =
         reports.iter().filter(|&r| is_safe(r)).count();
 
-    Ok(sum.to_string())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -71,7 +52,7 @@ mod tests {
     #[test]
     fn test_parser() -> miette::Result<()> {
         let input = "7 6 4 2 1";
-        let expected = vec![7, 6, 4, 2, 1];
+        let expected = vec![vec![7, 6, 4, 2, 1]];
         let actual = parser::parse(input)?;
         assert_eq!(expected, actual);
         Ok(())
@@ -85,7 +66,7 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!("2", process(input)?);
+        assert_eq!(2, process(input)?);
         Ok(())
     }
 }
@@ -1,16 +1,4 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
-#[derive(Error, Debug, Diagnostic)]
-enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-
-    #[error("Parse error: `{0}`")]
-    #[diagnostic(code(aoc::parser_error))]
-    ParseError(String),
-}
+use aoc_core::AocError;
 
 #[derive(Debug)]
 enum Opcode {
@@ -18,34 +6,34 @@ enum Opcode {
 }
 
 mod parser {
+    use aoc_core::parse::{IResult, tag};
     use nom::{
-        IResult, Parser,
+        Parser,
         branch::alt,
-        bytes::{
-            complete::{take, take_until},
-            tag,
-        },
+        bytes::complete::{take, take_until},
         character::{char, complete::u32},
         combinator::map,
+        error::context,
         multi::many0,
         sequence::{delimited, preceded, separated_pair},
     };
 
     use super::*;
 
-    fn mul_opcode(
-        input: &str,
-    ) -> IResult<&str, Opcode, ()> {
-        delimited(
-            tag("mul("),
-            separated_pair(u32, char(','), u32),
-            char(')'),
+    fn mul_opcode(input: &str) -> IResult<'_, Opcode> {
+        context(
+            "mul instruction",
+            delimited(
+                tag("mul("),
+                separated_pair(u32, char(','), u32),
+                char(')'),
+            ),
         )
         .map(|(x, y)| Opcode::Mul(x, y))
         .parse(input)
     }
 
-    fn find_mul(input: &str) -> IResult<&str, Opcode, ()> {
+    fn find_mul(input: &str) -> IResult<'_, Opcode> {
         preceded(
             take_until("mul("),
             map(mul_opcode, |pair| pair),
@@ -53,9 +41,7 @@ mod parser {
         .parse(input)
     }
 
-    fn opcodes(
-        input: &str,
-    ) -> IResult<&str, Vec<Opcode>, ()> {
+    fn opcodes(input: &str) -> IResult<'_, Vec<Opcode>> {
         let skip_one_char = map(take(1usize), |_| None);
         let next_opcode = map(find_mul, Some);
 
@@ -72,20 +58,20 @@ mod parser {
     pub fn parse(
         input: &str,
     ) -> Result<Vec<Opcode>, AocError> {
-        opcodes(input).map(|x| x.1).map_err(|_| {
-            AocError::ParseError(input.to_string())
-        })
+        opcodes(input)
+            .map(|x| x.1)
+            .map_err(|e| AocError::from_nom(input, e))
     }
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let ops = parser::parse(input)?;
     let sum =
         ops.iter().fold(0, |acc, opcode| match opcode {
             Opcode::Mul(x, y) => acc + x * y,
         });
-    Ok(sum.to_string())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -95,7 +81,7 @@ mod tests {
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!("161", process(input)?);
+        assert_eq!(161, process(input)?);
         Ok(())
     }
 }
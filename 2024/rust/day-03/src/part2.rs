@@ -1,12 +1,4 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
-#[derive(Error, Debug, Diagnostic)]
-enum AocError {
-    #[error("Parse error:\n`{0}`.\nInput: `{1}`")]
-    #[diagnostic(code(aoc::parser_error))]
-    ParseError(String, String),
-}
+use aoc_core::AocError;
 
 #[derive(PartialEq, Clone, Debug)]
 enum Opcode {
@@ -16,34 +8,36 @@ enum Opcode {
 }
 
 mod parser {
+    use aoc_core::parse::{IResult, tag};
     use nom::{
-        IResult, Parser,
+        Parser,
         branch::alt,
-        bytes::complete::tag,
         character::{
             char,
             complete::{anychar, u32},
         },
         combinator::value,
+        error::context,
         multi::{many_till, many1},
         sequence::{delimited, separated_pair},
     };
 
     use super::*;
 
-    fn mul_opcode(
-        input: &str,
-    ) -> IResult<&str, Opcode, ()> {
-        delimited(
-            tag("mul("),
-            separated_pair(u32, char(','), u32),
-            char(')'),
+    fn mul_opcode(input: &str) -> IResult<'_, Opcode> {
+        context(
+            "mul instruction",
+            delimited(
+                tag("mul("),
+                separated_pair(u32, char(','), u32),
+                char(')'),
+            ),
         )
         .map(|(x, y)| Opcode::Mul(x, y))
         .parse(input)
     }
 
-    fn opcode(input: &str) -> IResult<&str, Opcode, ()> {
+    fn opcode(input: &str) -> IResult<'_, Opcode> {
         alt((
             value(Opcode::Do, tag("do()")),
             value(Opcode::Dont, tag("don't()")),
@@ -52,9 +46,7 @@ mod parser {
         .parse(input)
     }
 
-    fn opcodes(
-        input: &str,
-    ) -> IResult<&str, Vec<Opcode>, ()> {
+    fn opcodes(input: &str) -> IResult<'_, Vec<Opcode>> {
         many1(many_till(anychar, opcode).map(|(_, op)| op))
             .parse(input)
     }
@@ -62,17 +54,14 @@ mod parser {
     pub fn parse(
         input: &str,
     ) -> Result<Vec<Opcode>, AocError> {
-        opcodes(input).map(|x| x.1).map_err(|e| {
-            AocError::ParseError(
-                e.to_string(),
-                input.to_string(),
-            )
-        })
+        opcodes(input)
+            .map(|x| x.1)
+            .map_err(|e| AocError::from_nom(input, e))
     }
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let ops = parser::parse(input)?;
     let (_, sum) = ops.iter().fold(
         (true, 0),
@@ -88,7 +77,7 @@ pub fn process(input: &str) -> miette::Result<String> {
             (false, Opcode::Mul(_, _)) => (false, sum),
         },
     );
-    Ok(sum.to_string())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -132,7 +121,7 @@ mod tests {
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!("48", process(input)?);
+        assert_eq!(48, process(input)?);
         Ok(())
     }
 }
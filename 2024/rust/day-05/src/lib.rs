@@ -1,4 +1,3 @@
-pub mod error;
 pub mod parser;
 pub mod part1;
 pub mod part2;
@@ -8,3 +7,13 @@ pub struct Puzzle {
     pub rules: Vec<(u32, u32)>,
     pub lines: Vec<Vec<u32>>,
 }
+
+aoc_core::register! {
+    year: 2024,
+    day: 5,
+    part1: part1::process,
+    part2: part2::process,
+}
+
+#[cfg(test)]
+aoc_test::examples!();
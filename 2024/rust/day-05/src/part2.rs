@@ -6,7 +6,7 @@ use std::{
 use crate::parser;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> miette::Result<u32> {
     let puzzle = parser::parse(input)?;
 
     let mut rules: HashMap<u32, HashSet<u32>> =
@@ -80,45 +80,5 @@ pub fn process(input: &str) -> miette::Result<String> {
         .collect();
 
     let result: u32 = middles.iter().sum();
-    Ok(result.to_string())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input: &'static str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-
-        assert_eq!("123", process(input)?);
-        Ok(())
-    }
+    Ok(result)
 }
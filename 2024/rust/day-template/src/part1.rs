@@ -1,5 +1,5 @@
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
+pub fn process(_input: &str) -> miette::Result<u64> {
     todo!("day ?? - part 1");
 }
 
@@ -11,7 +11,7 @@ mod tests {
     fn test_process() -> miette::Result<()> {
         todo!("day ?? - test not implemented yet");
         let input = "";
-        assert_eq!("", process(input)?);
+        assert_eq!(0, process(input)?);
         Ok(())
     }
 }
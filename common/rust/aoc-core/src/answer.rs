@@ -0,0 +1,172 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use crate::ocr;
+
+/// What a solution comes up with.
+///
+/// Answers read from somewhere else (the site,
+/// `answers.toml`, an example) go through
+/// [`Answer::parse`], so `" 143\n"` and `143_u32`
+/// compare equal. `"0143"` stays a string: the
+/// leading zero may be part of the answer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    /// Several lines of letters drawn with `#`
+    /// in a font [`ocr::read`] doesn't know,
+    /// which still have to be read off.
+    Art(String),
+}
+
+impl Answer {
+    /// Reads an answer written out as text: a
+    /// number if it reads back exactly the same
+    /// (so not `007` or `+5`), the letters drawn
+    /// if it spans several lines and a string
+    /// otherwise. Art that [`ocr::read`] can't
+    /// make out is kept as [`Answer::Art`].
+    pub fn parse(s: &str) -> Answer {
+        let s = s.trim_end();
+        if s.trim_start().contains('\n') {
+            return match ocr::read(s) {
+                Ok(letters) => Answer::Str(letters),
+                Err(_) => Answer::art(s),
+            };
+        }
+        let s = s.trim_start();
+        match s.parse::<i128>() {
+            Ok(n) if n.to_string() == s => Answer::Int(n),
+            _ => Answer::Str(s.to_string()),
+        }
+    }
+
+    /// Letter art, without the blank lines around
+    /// it or trailing spaces on its rows.
+    pub fn art(s: &str) -> Answer {
+        let rows: Vec<&str> = s
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|row| row.is_empty())
+            .collect();
+        let len = rows
+            .iter()
+            .rposition(|row| !row.is_empty())
+            .map_or(0, |last| last + 1);
+        Answer::Art(rows[..len].join("\n"))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) | Answer::Art(s) => {
+                f.write_str(s)
+            }
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Answer::parse(s))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::parse(s)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::parse(&s)
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),+) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::Int(n.into())
+                }
+            }
+        )+
+    };
+}
+
+from_int!(
+    u8, u16, u32, u64, i8, i16, i32, i64, i128
+);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("143", Answer::Int(143))]
+    #[case(" 143\n", Answer::Int(143))]
+    #[case("007", Answer::Str("007".to_string()))]
+    #[case("+5", Answer::Str("+5".to_string()))]
+    #[case("01029498", Answer::Str("01029498".to_string()))]
+    #[case("-7", Answer::Int(-7))]
+    #[case("abc,def", Answer::Str("abc,def".to_string()))]
+    #[case("1,2,3\n", Answer::Str("1,2,3".to_string()))]
+    #[case(
+        "\n#..#  \n####\n\n",
+        Answer::Art("#..#\n####".to_string())
+    )]
+    #[case(
+        ".##..###.\n#..#.#..#\n#..#.###.\n\
+         ####.#..#\n#..#.#..#\n#..#.###.\n",
+        Answer::Str("AB".to_string())
+    )]
+    fn test_parse(
+        #[case] input: &str,
+        #[case] expected: Answer,
+    ) {
+        assert_eq!(expected, Answer::parse(input));
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::Int(143), 143_u32.into());
+        assert_eq!(Answer::Int(143), 143_usize.into());
+        assert_eq!(Answer::Int(143), "143".into());
+        assert_eq!(
+            Answer::Int(i128::from(u64::MAX)),
+            u64::MAX.into()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("143", Answer::Int(143).to_string());
+        assert_eq!(
+            "#..#\n####",
+            Answer::art("#..#\n####\n").to_string()
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use miette::{
+    Diagnostic, LabeledSpan, NamedSource, SourceSpan,
+};
+use thiserror::Error;
+
+use crate::parse;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("Parse error at line {line}, column {column}")]
+    #[diagnostic(code(aoc::parser_error))]
+    ParseError {
+        #[source_code]
+        src: NamedSource<String>,
+        /// What was expected at the failure
+        /// point, followed by the active
+        /// context labels.
+        #[label(collection)]
+        labels: Vec<LabeledSpan>,
+        line: usize,
+        column: usize,
+    },
+
+    #[error(
+        "Row {row} of the grid is {len} wide, not {width}"
+    )]
+    #[diagnostic(code(aoc::ragged_grid))]
+    RaggedGrid {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("this row")]
+        span: SourceSpan,
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+
+    /// Several parts of the input failed to
+    /// parse, e.g. several lines.
+    #[error("Parse errors at {}", list(.locations))]
+    #[diagnostic(code(aoc::parser_error))]
+    ParseErrors {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label(collection)]
+        labels: Vec<LabeledSpan>,
+        /// Line and column of each failure.
+        locations: Vec<(usize, usize)>,
+    },
+
+    #[error("No input for {year} day {day:02}")]
+    #[diagnostic(code(aoc::missing_input))]
+    MissingInput {
+        year: u16,
+        day: u8,
+        #[help]
+        help: String,
+    },
+
+    #[error(
+        "Letter art {height} rows high isn't in a known font"
+    )]
+    #[diagnostic(
+        code(aoc::unknown_font),
+        help("Only letters 6 or 10 rows high can be read")
+    )]
+    UnknownFont { height: usize },
+
+    #[error(
+        "Letter {position} of the art isn't one that can be read"
+    )]
+    #[diagnostic(code(aoc::unknown_letter))]
+    UnknownLetter {
+        position: usize,
+        /// The letter as it was drawn, so it can
+        /// be added to the font.
+        #[help]
+        glyph: String,
+    },
+}
+
+impl AocError {
+    /// Converts a failed nom parse of `input`
+    /// into a parse error pointing at the
+    /// place it failed, with every active
+    /// context label underlined.
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<parse::Error<'_>>,
+    ) -> Self {
+        let (labels, line, column) =
+            labels(input, err, true);
+        AocError::ParseError {
+            src: NamedSource::new(
+                "input",
+                input.to_string(),
+            ),
+            labels,
+            line,
+            column,
+        }
+    }
+
+    /// Converts several failed nom parses of
+    /// parts of `input`, e.g. one per line, into
+    /// a single error underlining all of them.
+    pub fn from_nom_all(
+        input: &str,
+        mut errs: Vec<nom::Err<parse::Error<'_>>>,
+    ) -> Self {
+        if errs.len() == 1 {
+            return AocError::from_nom(
+                input,
+                errs.remove(0),
+            );
+        }
+        let mut all = vec![];
+        let mut locations = vec![];
+        for (i, err) in errs.into_iter().enumerate() {
+            let (labels, line, column) =
+                labels(input, err, i == 0);
+            all.extend(labels);
+            locations.push((line, column));
+        }
+        AocError::ParseErrors {
+            src: NamedSource::new(
+                "input",
+                input.to_string(),
+            ),
+            labels: all,
+            locations,
+        }
+    }
+}
+
+/// Labels for where `err` failed in `input`, and
+/// the line and column it failed at.
+fn labels(
+    input: &str,
+    err: nom::Err<parse::Error<'_>>,
+    primary: bool,
+) -> (Vec<LabeledSpan>, usize, usize) {
+    let err = match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        nom::Err::Incomplete(_) => parse::Error {
+            input: &input[input.len()..],
+            expected: parse::Expected::Kind(
+                nom::error::ErrorKind::Eof,
+            ),
+            context: vec![],
+        },
+    };
+
+    let at = err.offset(input);
+    let (line, column) = location(input, at);
+    let len = input[at..]
+        .chars()
+        .next()
+        .map_or(0, char::len_utf8);
+
+    let label = Some(format!("expected {}", err.expected));
+    let expected = if primary {
+        LabeledSpan::new_primary_with_span(label, (at, len))
+    } else {
+        LabeledSpan::new_with_span(label, (at, len))
+    };
+    let context = err.context.iter().map(|(rest, ctx)| {
+        let from = offset(input, rest).min(at);
+        LabeledSpan::new(
+            Some(format!("while parsing {ctx}")),
+            from,
+            at - from,
+        )
+    });
+
+    (
+        std::iter::once(expected).chain(context).collect(),
+        line,
+        column,
+    )
+}
+
+/// `line 2, column 5; line 4, column 1`
+fn list(locations: &[(usize, usize)]) -> String {
+    locations
+        .iter()
+        .map(|(line, column)| {
+            format!("line {line}, column {column}")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Byte offset of `rest` inside `input`.
+///
+/// Nom hands back subslices of its input, so the
+/// pointers tell us where `rest` starts. Anything
+/// else is treated as a suffix of `input`.
+pub(crate) fn offset(input: &str, rest: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let at = rest.as_ptr() as usize;
+
+    let mut offset =
+        if (start..=start + input.len()).contains(&at) {
+            at - start
+        } else {
+            input.len().saturating_sub(rest.len())
+        };
+
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// 1-based line and column of the byte at
+/// `offset`.
+fn location(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        Parser,
+        character::complete::{line_ending, u32},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+    use rstest::rstest;
+
+    use super::*;
+    use crate::parse::{IResult, tag};
+
+    fn rules(input: &str) -> IResult<'_, Vec<(u32, u32)>> {
+        separated_list1(
+            line_ending,
+            context(
+                "rule",
+                separated_pair(u32, tag("|"), u32),
+            ),
+        )
+        .parse(input)
+    }
+
+    #[rstest]
+    #[case("1\n2\n3", 0, (1, 1))]
+    #[case("1\n2\n3", 2, (2, 1))]
+    #[case("12\n345", 5, (2, 3))]
+    #[case("ab", 2, (1, 3))]
+    fn test_location(
+        #[case] input: &str,
+        #[case] offset: usize,
+        #[case] expected: (usize, usize),
+    ) {
+        assert_eq!(expected, location(input, offset));
+    }
+
+    #[test]
+    fn test_offset_of_subslice() {
+        let input = "1\n2\nx";
+        assert_eq!(4, offset(input, &input[4..]));
+        let copy = String::from("2\nx");
+        assert_eq!(2, offset(input, &copy));
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let input = "1|2\n3|4\n5-6";
+        // `separated_list1` would stop before the bad
+        // line, so parse the last line on its own.
+        let err = rules(&input[8..]).unwrap_err();
+
+        let AocError::ParseError {
+            labels,
+            line,
+            column,
+            ..
+        } = AocError::from_nom(input, err)
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((3, 2), (line, column));
+        assert_eq!(2, labels.len());
+
+        let (expected, context) = (&labels[0], &labels[1]);
+        assert!(expected.primary());
+        assert_eq!(Some("expected `|`"), expected.label());
+        assert_eq!(
+            (9, 1),
+            (expected.offset(), expected.len())
+        );
+        assert_eq!(
+            Some("while parsing rule"),
+            context.label()
+        );
+        assert_eq!(
+            (8, 1),
+            (context.offset(), context.len())
+        );
+    }
+
+    #[test]
+    fn test_message() {
+        let input = "1|2\nx|4";
+        let err = separated_list1(line_ending, rules)
+            .parse(&input[4..])
+            .unwrap_err();
+        assert_eq!(
+            "Parse error at line 2, column 1",
+            AocError::from_nom(input, err).to_string()
+        );
+    }
+}
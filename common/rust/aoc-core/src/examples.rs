@@ -0,0 +1,217 @@
+//! Puzzle examples kept in a day's `examples/`
+//! directory.
+//!
+//! Every `{name}.txt` is an example input. Its
+//! expected answers either sit next to it in
+//! `{name}.part1.answer` and
+//! `{name}.part2.answer`, or at the top of the
+//! input as front matter:
+//!
+//! ```text
+//! ---
+//! part1: 143
+//! part2: 123
+//! ---
+//! 47|53
+//! ...
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::Part;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    /// File name without the `.txt`.
+    pub name: String,
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    /// Splits off the front matter of an example
+    /// file, if it has any.
+    pub fn parse(name: &str, contents: &str) -> Example {
+        let mut answers = [None, None];
+        let input = match front_matter(contents) {
+            Some((header, input)) => {
+                for line in header.lines() {
+                    let Some((key, value)) =
+                        line.split_once(':')
+                    else {
+                        continue;
+                    };
+                    if let Ok(part) =
+                        key.trim().parse::<Part>()
+                    {
+                        answers
+                            [part.number() as usize - 1] =
+                            Some(value.trim().to_string());
+                    }
+                }
+                input
+            }
+            None => contents,
+        };
+        Example {
+            name: name.to_string(),
+            input: input.to_string(),
+            answers,
+        }
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        self.answers[part.number() as usize - 1].as_deref()
+    }
+}
+
+fn front_matter(contents: &str) -> Option<(&str, &str)> {
+    let rest = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))?;
+    let end = rest.find("\n---")?;
+    let after = &rest[end + "\n---".len()..];
+    let input = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+    Some((&rest[..end], input))
+}
+
+/// Where the expected answer of the example at
+/// `input` is kept, e.g. `example-1.part2.answer`
+/// for `example-1.txt`.
+pub fn answer_path(input: &Path, part: Part) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    input
+        .with_file_name(format!("{stem}.part{part}.answer"))
+}
+
+/// Loads every example in `dir`, sorted by name.
+/// A missing directory just has no examples.
+pub fn load_dir(dir: &Path) -> io::Result<Vec<Example>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(vec![]);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut inputs = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt")
+        {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    inputs
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let mut example = Example::parse(
+                &name,
+                &fs::read_to_string(&path)?,
+            );
+            for part in Part::ALL {
+                match fs::read_to_string(answer_path(
+                    &path, part,
+                )) {
+                    Ok(answer) => {
+                        example.answers
+                            [part.number() as usize - 1] =
+                            Some(answer.trim().to_string());
+                    }
+                    Err(e)
+                        if e.kind()
+                            == io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(example)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_front_matter() {
+        let example = Example::parse(
+            "example-1",
+            "---\npart1: 143\npart2: 123\n---\n47|53\n",
+        );
+        assert_eq!("47|53\n", example.input);
+        assert_eq!(Some("143"), example.answer(Part::One));
+        assert_eq!(Some("123"), example.answer(Part::Two));
+    }
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        let example =
+            Example::parse("example-1", "3   4\n");
+        assert_eq!("3   4\n", example.input);
+        assert_eq!(None, example.answer(Part::One));
+    }
+
+    #[test]
+    fn test_answer_path() {
+        assert_eq!(
+            Path::new("examples/example-1.part2.answer"),
+            answer_path(
+                Path::new("examples/example-1.txt"),
+                Part::Two
+            )
+        );
+    }
+
+    #[test]
+    fn test_load_dir() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("b.txt"),
+            "---\npart2: 9\n---\nb\n",
+        )?;
+        fs::write(dir.path().join("a.txt"), "a\n")?;
+        fs::write(
+            dir.path().join("a.part1.answer"),
+            "18\n",
+        )?;
+
+        let examples = load_dir(dir.path())?;
+        assert_eq!(
+            vec![
+                Example {
+                    name: "a".to_string(),
+                    input: "a\n".to_string(),
+                    answers: [Some("18".to_string()), None],
+                },
+                Example {
+                    name: "b".to_string(),
+                    input: "b\n".to_string(),
+                    answers: [None, Some("9".to_string())],
+                },
+            ],
+            examples
+        );
+        assert!(
+            load_dir(&dir.path().join("missing"))?
+                .is_empty()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,580 @@
+//! Rectangular grids of cells, the shape most
+//! puzzle maps come in.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use miette::NamedSource;
+
+use crate::{
+    AocError,
+    error::offset,
+    point::{Direction, IVec2, Point},
+};
+
+/// Cells stored row by row.
+///
+/// Cells are looked up by [`Point`] (or an
+/// `(x, y)` tuple), with `x` counting columns
+/// from the left and `y` rows from the top.
+/// Coordinates are signed, so that stepping off
+/// the edge is just a failed lookup.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// One cell per character, one row per line.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        Grid::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// One cell per character, one row per line,
+    /// each character turned into a cell by
+    /// `cell`. Every line has to be as long as
+    /// the first; blank lines at the end are
+    /// ignored.
+    pub fn parse_with(
+        input: &str,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<Self, AocError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        let rows = input.trim_end_matches(['\r', '\n']);
+        for line in rows.lines() {
+            let len = line.chars().count();
+            let width = *width.get_or_insert(len);
+            if len != width {
+                return Err(AocError::RaggedGrid {
+                    src: NamedSource::new(
+                        "input",
+                        input.to_string(),
+                    ),
+                    span: (offset(input, line), line.len())
+                        .into(),
+                    row: height + 1,
+                    len,
+                    width,
+                });
+            }
+            cells.extend(line.chars().map(&mut cell));
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    /// A grid `width` cells wide, from its cells
+    /// row by row.
+    pub(crate) fn new(width: usize, cells: Vec<T>) -> Self {
+        Grid {
+            width,
+            height: cells
+                .len()
+                .checked_div(width)
+                .unwrap_or(0),
+            cells,
+        }
+    }
+
+    /// A grid with `cell(pos)` at every position.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut(Point) -> T,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| {
+                (0..width).map(move |x| {
+                    IVec2::new(x as i32, y as i32)
+                })
+            })
+            .map(&mut cell)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Where `pos` is in `cells`, if it's on the
+    /// grid.
+    fn index(&self, pos: Point) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+        (x < self.width && y < self.height)
+            .then_some(y * self.width + x)
+    }
+
+    fn pos(&self, index: usize) -> Point {
+        IVec2::new(
+            (index % self.width) as i32,
+            (index / self.width) as i32,
+        )
+    }
+
+    pub fn contains(&self, pos: impl Into<Point>) -> bool {
+        self.index(pos.into()).is_some()
+    }
+
+    pub fn get(&self, pos: impl Into<Point>) -> Option<&T> {
+        self.index(pos.into()).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(
+        &mut self,
+        pos: impl Into<Point>,
+    ) -> Option<&mut T> {
+        self.index(pos.into()).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.pos(i), cell))
+    }
+
+    /// Positions of the cells `is` holds for.
+    pub fn positions(
+        &self,
+        mut is: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = Point> {
+        self.iter()
+            .filter(move |(_, cell)| is(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    fn around<'a>(
+        &'a self,
+        pos: Point,
+        dirs: &'a [Direction],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        dirs.iter().filter_map(move |dir| {
+            let pos = pos + dir.step();
+            Some((pos, self.get(pos)?))
+        })
+    }
+
+    /// The cells up, right, down and left of
+    /// `pos` that are on the grid.
+    pub fn neighbours4(
+        &self,
+        pos: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.around(pos.into(), &Direction::ORTHOGONAL)
+    }
+
+    /// The cells around `pos`, diagonals
+    /// included, that are on the grid,
+    /// clockwise from the one above.
+    pub fn neighbours8(
+        &self,
+        pos: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.around(pos.into(), &Direction::ALL)
+    }
+
+    /// The cells from `from` towards `dir`, up to
+    /// the edge of the grid, without `from`
+    /// itself.
+    pub fn ray(
+        &self,
+        from: impl Into<Point>,
+        dir: Direction,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        dir.ray(from.into())
+            .map_while(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a width of 0.
+        self.cells
+            .chunks(self.width.max(1))
+            .take(self.height)
+    }
+
+    pub fn column(
+        &self,
+        x: usize,
+    ) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is off the grid"
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Cells from `from` onwards towards `dir`,
+    /// until the edge of the grid.
+    fn line(
+        &self,
+        from: Point,
+        dir: Direction,
+    ) -> impl Iterator<Item = &T> {
+        self.get(from).into_iter().chain(
+            self.ray(from, dir).map(|(_, cell)| cell),
+        )
+    }
+
+    /// Lines going down and to the right, from
+    /// the bottom left corner to the top right.
+    pub fn diagonals(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        let (w, h) =
+            (self.width as i32, self.height as i32);
+        (0..w + h - 1).map(move |i| {
+            let from = IVec2::new(
+                (i - h + 1).max(0),
+                (h - 1 - i).max(0),
+            );
+            self.line(from, Direction::SE)
+        })
+    }
+
+    /// Lines going down and to the left, from the
+    /// top left corner to the bottom right.
+    pub fn anti_diagonals(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        let (w, h) =
+            (self.width as i32, self.height as i32);
+        (0..w + h - 1).map(move |i| {
+            let from = IVec2::new(
+                i.min(w - 1),
+                (i - w + 1).max(0),
+            );
+            self.line(from, Direction::SW)
+        })
+    }
+
+    pub fn map<U>(
+        &self,
+        f: impl FnMut(&T) -> U,
+    ) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(
+        width: usize,
+        height: usize,
+        value: T,
+    ) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |pos| {
+            self[(pos.y, pos.x)].clone()
+        })
+    }
+
+    /// Turned a quarter clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let h = self.height as i32;
+        Grid::from_fn(self.height, self.width, |pos| {
+            self[(pos.y, h - 1 - pos.x)].clone()
+        })
+    }
+
+    /// Turned a quarter anticlockwise.
+    pub fn rotate_left(&self) -> Self {
+        let w = self.width as i32;
+        Grid::from_fn(self.height, self.width, |pos| {
+            self[(w - 1 - pos.y, pos.x)].clone()
+        })
+    }
+}
+
+impl<T, P: Into<Point>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &T {
+        let pos = pos.into();
+        self.get(pos).unwrap_or_else(|| {
+            panic!("{pos:?} is off the grid")
+        })
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let pos = pos.into();
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!("{pos:?} is off the grid")
+        })
+    }
+}
+
+/// Draws the grid back the way it was parsed.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(INPUT).unwrap()
+    }
+
+    fn collect<'a>(
+        lines: impl Iterator<
+            Item = impl Iterator<Item = &'a char>,
+        >,
+    ) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(INPUT, grid.to_string());
+        assert_eq!(
+            grid,
+            Grid::parse("abc\r\ndef\r\n").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("abc\ndef\n\n")]
+    #[case("abc\ndef\n\n\n")]
+    #[case("abc\r\ndef\r\n\r\n")]
+    fn test_parse_trailing_blank_lines(
+        #[case] input: &str,
+    ) {
+        assert_eq!(grid(), Grid::parse(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let grid =
+            Grid::parse_with("12\n34", |c| c.to_digit(10))
+                .unwrap();
+        assert_eq!(Some(&Some(3)), grid.get((0, 1)));
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let err = Grid::parse("abc\nde\nfgh").unwrap_err();
+        let AocError::RaggedGrid {
+            span,
+            row,
+            len,
+            width,
+            ..
+        } = err
+        else {
+            panic!("expected a ragged grid error");
+        };
+        assert_eq!((2, 2, 3), (row, len, width));
+        assert_eq!((4, 2), (span.offset(), span.len()));
+    }
+
+    #[rstest]
+    #[case((0, 0), Some('a'))]
+    #[case((2, 1), Some('f'))]
+    #[case((3, 0), None)]
+    #[case((0, 2), None)]
+    #[case((-1, 0), None)]
+    #[case((0, -1), None)]
+    fn test_get(
+        #[case] pos: (i32, i32),
+        #[case] expected: Option<char>,
+    ) {
+        assert_eq!(expected.as_ref(), grid().get(pos));
+        assert_eq!(
+            expected.is_some(),
+            grid().contains(pos)
+        );
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut grid = grid();
+        grid[(1, 1)] = '*';
+        assert_eq!("abc\nd*f\n", grid.to_string());
+    }
+
+    #[test]
+    fn test_iter() {
+        assert_eq!(
+            vec![IVec2::new(1, 0), IVec2::new(1, 1)],
+            grid()
+                .positions(|c| "be".contains(*c))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some((IVec2::new(2, 1), &'f')),
+            grid().iter().last()
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let cells = |it: &mut dyn Iterator<
+            Item = (Point, &char),
+        >| {
+            it.map(|(_, c)| *c).collect::<String>()
+        };
+        assert_eq!(
+            "bd",
+            cells(&mut grid.neighbours4((0, 0)))
+        );
+        assert_eq!(
+            "cea",
+            cells(&mut grid.neighbours4((1, 0)))
+        );
+        assert_eq!(
+            "bed",
+            cells(&mut grid.neighbours8((0, 0)))
+        );
+        assert_eq!(
+            "cfeda",
+            cells(&mut grid.neighbours8((1, 0)))
+        );
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = grid();
+        assert_eq!(
+            vec![
+                (IVec2::new(1, 0), &'b'),
+                (IVec2::new(2, 0), &'c')
+            ],
+            grid.ray((0, 0), Direction::E)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            0,
+            grid.ray((0, 0), Direction::N).count()
+        );
+        assert_eq!(
+            Some((IVec2::new(2, 1), &'f')),
+            grid.ray((1, 0), Direction::SE).last()
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(['d', 'e', 'f'], grid.row(1));
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(
+            vec!["ad", "be", "cf"],
+            collect(grid.columns())
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = grid();
+        assert_eq!(
+            vec!["d", "ae", "bf", "c"],
+            collect(grid.diagonals())
+        );
+        assert_eq!(
+            vec!["a", "bd", "ce", "f"],
+            collect(grid.anti_diagonals())
+        );
+    }
+
+    #[test]
+    fn test_turns() {
+        let grid = grid();
+        assert_eq!(
+            "ad\nbe\ncf\n",
+            grid.transpose().to_string()
+        );
+        assert_eq!(
+            "da\neb\nfc\n",
+            grid.rotate_right().to_string()
+        );
+        assert_eq!(
+            "cf\nbe\nad\n",
+            grid.rotate_left().to_string()
+        );
+        assert_eq!(
+            grid,
+            grid.rotate_right()
+                .rotate_right()
+                .rotate_left()
+                .rotate_left()
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = Grid::parse("").unwrap();
+        assert_eq!((0, 0), (grid.width(), grid.height()));
+        assert_eq!(0, grid.rows().count());
+        assert_eq!(0, grid.diagonals().count());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{AocError, Solution};
+
+/// Overrides the inputs directory.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+/// Where puzzle inputs are read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Inputs directory laid out as
+    /// `{year}/day-{day:02}.txt`.
+    Dir(PathBuf),
+    /// A single file, whichever day is asked for.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Dir(default_dir())
+    }
+}
+
+/// Root of the repository this crate was built
+/// from.
+pub fn repo_root() -> PathBuf {
+    // common/rust/aoc-core -> repository root
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(3)
+        .expect("aoc-core lives in common/rust")
+        .to_path_buf()
+}
+
+/// Directory of a day's crate, e.g.
+/// `2024/rust/day-05`.
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    repo_root()
+        .join(year.to_string())
+        .join("rust")
+        .join(format!("day-{day:02}"))
+}
+
+/// `$AOC_INPUTS`, or `inputs/` at the root of the
+/// repository.
+pub fn default_dir() -> PathBuf {
+    std::env::var_os(INPUTS_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| repo_root().join("inputs"))
+}
+
+/// Path of a day's input inside an inputs
+/// directory.
+pub fn path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day-{day:02}.txt"))
+}
+
+impl Source {
+    /// Loads the input of a registered solution.
+    ///
+    /// When reading from an inputs directory, the
+    /// `input1.txt`/`input2.txt` files older
+    /// tooling left next to the day crate are
+    /// used as a fallback.
+    pub fn load(
+        &self,
+        solution: &dyn Solution,
+    ) -> Result<String, AocError> {
+        let legacy = solution.dir().map(|dir| {
+            Path::new(dir).join(format!(
+                "input{}.txt",
+                solution.part()
+            ))
+        });
+        self.load_day(
+            solution.year(),
+            solution.day(),
+            legacy,
+        )
+    }
+
+    /// Loads a day's input, trying `fallback` if
+    /// it isn't in the inputs directory.
+    pub fn load_day(
+        &self,
+        year: u16,
+        day: u8,
+        fallback: Option<PathBuf>,
+    ) -> Result<String, AocError> {
+        let candidates = match self {
+            Source::Stdin => {
+                return Ok(
+                    io::read_to_string(io::stdin())?,
+                );
+            }
+            Source::File(file) => vec![file.clone()],
+            Source::Dir(dir) => {
+                std::iter::once(path(dir, year, day))
+                    .chain(fallback)
+                    .collect()
+            }
+        };
+
+        for candidate in &candidates {
+            match std::fs::read_to_string(candidate) {
+                Ok(input) => return Ok(input),
+                Err(e)
+                    if e.kind()
+                        == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let searched = candidates
+            .iter()
+            .map(|p| format!("  {}", p.display()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(AocError::MissingInput {
+            year,
+            day,
+            help: format!(
+                "Fetch it with `just fetch-input {year} day-{day:02}`, \
+                 point `{INPUTS_ENV}` at another inputs directory \
+                 or pass it with `--input <path>` or `--stdin`.\n\
+                 Looked in:\n{searched}"
+            ),
+        })
+    }
+}
+
+/// Loads a day's input for a benchmark, printing
+/// the diagnostic and exiting if there is none.
+pub fn for_bench(year: u16, day: u8) -> String {
+    Source::default()
+        .load_day(year, day, None)
+        .unwrap_or_else(|e| {
+            eprintln!("{:?}", miette::Report::new(e));
+            std::process::exit(1);
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{Answer, Part, solution::Process};
+
+    fn answer(input: &str) -> miette::Result<Answer> {
+        Ok(input.into())
+    }
+
+    #[test]
+    fn test_path() {
+        assert_eq!(
+            Path::new("inputs/2024/day-05.txt"),
+            path(Path::new("inputs"), 2024, 5)
+        );
+    }
+
+    #[test]
+    fn test_day_dir() {
+        assert!(
+            day_dir(2024, 5).ends_with("2024/rust/day-05")
+        );
+    }
+
+    #[test]
+    fn test_load_from_dir() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("2024")).unwrap();
+        fs::write(path(dir.path(), 2024, 5), "47|53")
+            .unwrap();
+
+        let source = Source::Dir(dir.path().to_path_buf());
+        assert_eq!(
+            "47|53",
+            source.load_day(2024, 5, None)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_from_file() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("example.txt");
+        fs::write(&file, "3   4").unwrap();
+
+        let source = Source::File(file);
+        assert_eq!(
+            "3   4",
+            source.load_day(2024, 1, None)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_falls_back_to_crate_dir()
+    -> miette::Result<()> {
+        let inputs = tempfile::tempdir().unwrap();
+        let day = tempfile::tempdir().unwrap();
+        fs::write(day.path().join("input2.txt"), "legacy")
+            .unwrap();
+
+        let dir: &'static str =
+            day.path().to_str().unwrap().to_string().leak();
+        let solution = Process {
+            year: 2024,
+            day: 1,
+            part: Part::Two,
+            process: answer,
+            dir,
+        };
+
+        let source =
+            Source::Dir(inputs.path().to_path_buf());
+        assert_eq!("legacy", source.load(&solution)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = Source::Dir(dir.path().to_path_buf());
+
+        let err = source
+            .load_day(
+                2024,
+                7,
+                Some(dir.path().join("input1.txt")),
+            )
+            .unwrap_err();
+        assert_eq!(
+            "No input for 2024 day 07",
+            err.to_string()
+        );
+
+        let AocError::MissingInput { help, .. } = err
+        else {
+            panic!("expected a missing input error");
+        };
+        assert!(help.contains("day-07.txt"));
+        assert!(help.contains("input1.txt"));
+    }
+}
@@ -0,0 +1,17 @@
+pub mod answer;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod solution;
+
+pub use answer::Answer;
+pub use error::AocError;
+pub use grid::Grid;
+#[doc(hidden)]
+pub use inventory;
+pub use point::{Direction, Point};
+pub use solution::{Part, Registry, Solution};
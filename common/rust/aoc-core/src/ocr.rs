@@ -0,0 +1,353 @@
+//! Reads the capital letters some puzzles draw
+//! instead of printing an answer, e.g.
+//!
+//! ```text
+//! #..#.####.#....#.....##.
+//! #..#.#....#....#....#..#
+//! ####.###..#....#....#..#
+//! #..#.#....#....#....#..#
+//! #..#.#....#....#....#..#
+//! #..#.####.####.####..##.
+//! ```
+//!
+//! Both fonts the puzzles use are known: letters
+//! six rows high (2016 day 8, 2019 days 8 and 11,
+//! 2021 day 13, 2022 day 10) and ten rows high
+//! (2018 day 10).
+
+use crate::AocError;
+
+/// Letters six rows high, each without the blank
+/// columns around it.
+const SMALL: &[(char, &str)] = &[
+    (
+        'A',
+        ".##.\n#..#\n#..#\n####\n#..#\n#..#",
+    ),
+    (
+        'B',
+        "###.\n#..#\n###.\n#..#\n#..#\n###.",
+    ),
+    (
+        'C',
+        ".##.\n#..#\n#...\n#...\n#..#\n.##.",
+    ),
+    (
+        'E',
+        "####\n#...\n###.\n#...\n#...\n####",
+    ),
+    (
+        'F',
+        "####\n#...\n###.\n#...\n#...\n#...",
+    ),
+    (
+        'G',
+        ".##.\n#..#\n#...\n#.##\n#..#\n.###",
+    ),
+    (
+        'H',
+        "#..#\n#..#\n####\n#..#\n#..#\n#..#",
+    ),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    (
+        'J',
+        "..##\n...#\n...#\n...#\n#..#\n.##.",
+    ),
+    (
+        'K',
+        "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#",
+    ),
+    (
+        'L',
+        "#...\n#...\n#...\n#...\n#...\n####",
+    ),
+    (
+        'O',
+        ".##.\n#..#\n#..#\n#..#\n#..#\n.##.",
+    ),
+    (
+        'P',
+        "###.\n#..#\n#..#\n###.\n#...\n#...",
+    ),
+    (
+        'R',
+        "###.\n#..#\n#..#\n###.\n#.#.\n#..#",
+    ),
+    (
+        'S',
+        ".###\n#...\n#...\n.##.\n...#\n###.",
+    ),
+    (
+        'U',
+        "#..#\n#..#\n#..#\n#..#\n#..#\n.##.",
+    ),
+    (
+        'Y',
+        "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#..",
+    ),
+    (
+        'Z',
+        "####\n...#\n..#.\n.#..\n#...\n####",
+    ),
+];
+
+/// Letters ten rows high, each without the blank
+/// columns around it.
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n\
+         ######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n\
+         #.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n\
+         #..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n\
+         #....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n\
+         ....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n\
+         ##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n\
+         #.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n\
+         #..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n\
+         #..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n\
+         ..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n\
+         ..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Whether a cell is drawn. Anything else, e.g.
+/// `.` or a space, is background.
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads the letters drawn in `art`, which may
+/// have blank rows and columns around it.
+pub fn read(art: &str) -> Result<String, AocError> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|row| row.chars().map(is_lit).collect())
+        .filter(|row: &Vec<bool>| row.contains(&true))
+        .collect();
+    let font = match rows.len() {
+        6 => SMALL,
+        10 => LARGE,
+        height => {
+            return Err(AocError::UnknownFont { height });
+        }
+    };
+
+    let width =
+        rows.iter().map(Vec::len).max().unwrap_or_default();
+    let lit = |row: &Vec<bool>, x: usize| {
+        row.get(x).copied().unwrap_or_default()
+    };
+    let blank_column =
+        |x: usize| rows.iter().all(|row| !lit(row, x));
+
+    // Letters are told apart by the blank columns
+    // between them.
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !blank_column(x) {
+            x += 1;
+        }
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|x| {
+                        if lit(row, x) { '#' } else { '.' }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let letter = font
+            .iter()
+            .find(|(_, known)| *known == glyph)
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| AocError::UnknownLetter {
+                position: letters.chars().count() + 1,
+                glyph: glyph.clone(),
+            })?;
+        letters.push(letter);
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Draws `letters` in `font` the way the
+    /// puzzles do, with `gap` blank columns after
+    /// each letter.
+    fn draw(
+        font: &[(char, &str)],
+        letters: &str,
+        gap: usize,
+    ) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|letter| {
+                let (_, glyph) = font
+                    .iter()
+                    .find(|(known, _)| *known == letter)
+                    .unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+        let height = glyphs[0].len();
+        (0..height)
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| {
+                        format!(
+                            "{}{}",
+                            glyph[y],
+                            ".".repeat(gap)
+                        )
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_read_small() -> miette::Result<()> {
+        let art = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.
+";
+        assert_eq!("HELLO", read(art)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_large() -> miette::Result<()> {
+        assert_eq!(
+            "ZEBRA",
+            read(&draw(LARGE, "ZEBRA", 2))?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::small(SMALL, 1)]
+    #[case::large(LARGE, 2)]
+    fn test_every_letter(
+        #[case] font: &[(char, &str)],
+        #[case] gap: usize,
+    ) -> miette::Result<()> {
+        let letters: String = font
+            .iter()
+            .map(|&(letter, _)| letter)
+            .collect();
+        assert_eq!(
+            letters,
+            read(&draw(font, &letters, gap))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_blocks_and_spaces() -> miette::Result<()> {
+        let art = draw(SMALL, "PZ", 1)
+            .replace('#', "█")
+            .replace('.', " ");
+        assert_eq!("PZ", read(&format!("\n{art}\n\n"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_letter() {
+        let art =
+            draw(SMALL, "AB", 1).replacen('#', ".", 1);
+        assert!(matches!(
+            read(&art),
+            Err(AocError::UnknownLetter {
+                position: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_font() {
+        assert!(matches!(
+            read("#\n#\n#"),
+            Err(AocError::UnknownFont { height: 3 })
+        ));
+    }
+}
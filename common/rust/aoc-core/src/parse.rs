@@ -0,0 +1,627 @@
+//! nom glue shared by every day: an error type
+//! that remembers what was expected where, and
+//! combinators for the shapes puzzle inputs
+//! come in.
+//!
+//! Everything that spans lines takes both `\n`
+//! and `\r\n` line endings.
+
+use std::{any::type_name, fmt, str::FromStr};
+
+use nom::{
+    Parser,
+    bytes::complete::take_till1,
+    character::complete::{
+        digit1, line_ending, one_of, space0, space1,
+    },
+    combinator::{eof, opt, recognize},
+    error::{
+        ContextError, ErrorKind, FromExternalError,
+        ParseError,
+    },
+    multi::{many0, separated_list1},
+    sequence::{delimited, preceded, terminated},
+};
+
+use crate::{AocError, Grid};
+
+/// Parser result using [`Error`] instead of nom's
+/// default error type.
+pub type IResult<'a, O> =
+    nom::IResult<&'a str, O, Error<'a>>;
+
+/// What the parser was looking for when it
+/// failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Char(char),
+    Tag(&'static str),
+    Kind(ErrorKind),
+    /// A number that fits in the named type.
+    Fits(&'static str),
+    /// A grid row as wide as the first.
+    Width(usize),
+    OneOf(Vec<Expected>),
+}
+
+impl Expected {
+    fn merge(self, other: Expected) -> Expected {
+        let mut all = match self {
+            Expected::OneOf(xs) => xs,
+            x => vec![x],
+        };
+        match other {
+            Expected::OneOf(xs) => all.extend(xs),
+            x => all.push(x),
+        }
+        all.dedup();
+        Expected::OneOf(all)
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Expected::Char(c) => write!(f, "`{c}`"),
+            Expected::Tag(tag) => write!(f, "`{tag}`"),
+            Expected::Kind(kind) => match kind {
+                ErrorKind::Digit => write!(f, "a number"),
+                ErrorKind::Space
+                | ErrorKind::MultiSpace => {
+                    write!(f, "whitespace")
+                }
+                ErrorKind::CrLf => {
+                    write!(f, "a line ending")
+                }
+                ErrorKind::Eof => write!(f, "end of input"),
+                kind => write!(f, "{}", kind.description()),
+            },
+            Expected::Fits(ty) => {
+                write!(f, "a number that fits in `{ty}`")
+            }
+            Expected::Width(width) => {
+                write!(f, "a row {width} wide")
+            }
+            Expected::OneOf(xs) => {
+                write!(f, "one of ")?;
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{x}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// nom error that keeps track of where parsing
+/// failed, what was expected there and which
+/// [`context`] labels were active at the time.
+///
+/// [`context`]: nom::error::context
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error<'a> {
+    /// Remaining input at the point of failure.
+    pub input: &'a str,
+    pub expected: Expected,
+    /// Context labels paired with the input they
+    /// started at, innermost first.
+    pub context: Vec<(&'a str, &'static str)>,
+}
+
+impl<'a> Error<'a> {
+    /// Byte offset of the failure inside
+    /// `source`, the full input handed to the
+    /// parser.
+    pub fn offset(&self, source: &str) -> usize {
+        crate::error::offset(source, self.input)
+    }
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(
+        input: &'a str,
+        kind: ErrorKind,
+    ) -> Self {
+        Error {
+            input,
+            expected: Expected::Kind(kind),
+            context: vec![],
+        }
+    }
+
+    fn append(
+        _input: &'a str,
+        _kind: ErrorKind,
+        other: Self,
+    ) -> Self {
+        // The innermost error is the most precise one.
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Error {
+            input,
+            expected: Expected::Char(c),
+            context: vec![],
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        // Report the branch that got the furthest, and
+        // list every alternative if they tie.
+        match self.input.len().cmp(&other.input.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => Error {
+                expected: self
+                    .expected
+                    .merge(other.expected),
+                ..self
+            },
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(
+        input: &'a str,
+        ctx: &'static str,
+        mut other: Self,
+    ) -> Self {
+        other.context.push((input, ctx));
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(
+        input: &'a str,
+        kind: ErrorKind,
+        _e: E,
+    ) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+/// Same as nom's `tag`, but remembers the tag
+/// itself so the error can say what was expected.
+pub fn tag<'a>(
+    tag: &'static str,
+) -> impl Parser<&'a str, Output = &'a str, Error = Error<'a>>
+{
+    move |input: &'a str| {
+        nom::bytes::complete::tag(tag).parse(input).map_err(
+            |e: nom::Err<Error<'a>>| {
+                e.map(|e| Error {
+                    expected: Expected::Tag(tag),
+                    ..e
+                })
+            },
+        )
+    }
+}
+
+/// Runs `parser` over the whole of `input`,
+/// which may end in line endings but nothing
+/// else.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> Result<O, AocError> {
+    terminated(parser, (many0(line_ending), eof))
+        .parse(input)
+        .map(|(_, output)| output)
+        .map_err(|e| AocError::from_nom(input, e))
+}
+
+/// Runs `parser` over each line of `input`,
+/// which it has to use up. Unlike `lines`, a bad
+/// line doesn't stop it: every line that fails
+/// is reported at once.
+pub fn each_line<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> Result<Vec<O>, AocError> {
+    let mut parser = terminated(parser, eof);
+    let mut parsed = vec![];
+    let mut errs = vec![];
+    for line in input.trim_end_matches(['\n', '\r']).lines()
+    {
+        match parser.parse(line) {
+            Ok((_, output)) => parsed.push(output),
+            Err(e) => errs.push(e),
+        }
+    }
+    if errs.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(AocError::from_nom_all(input, errs))
+    }
+}
+
+/// Turns digits into `T`, pointing at them if
+/// they don't fit.
+fn parse_digits<'a, T: FromStr>(
+    input: &'a str,
+    rest: &'a str,
+    digits: &str,
+) -> IResult<'a, T> {
+    match digits.parse() {
+        Ok(n) => Ok((rest, n)),
+        Err(_) => Err(nom::Err::Error(Error {
+            input,
+            expected: Expected::Fits(type_name::<T>()),
+            context: vec![],
+        })),
+    }
+}
+
+/// A number without a sign, e.g. `143`.
+pub fn number<T: FromStr>(input: &str) -> IResult<'_, T> {
+    let (rest, digits) = digit1(input)?;
+    parse_digits(input, rest, digits)
+}
+
+/// A number with an optional sign, e.g. `-7` or
+/// `+3`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<'_, T> {
+    let (rest, digits) =
+        recognize((opt(one_of("+-")), digit1))
+            .parse(input)?;
+    parse_digits(
+        input,
+        rest,
+        digits.trim_start_matches('+'),
+    )
+}
+
+/// Numbers on one line, separated by spaces or
+/// tabs, e.g. `7 6 4 2 1` or `3   4`.
+pub fn numbers<T: FromStr>(
+    input: &str,
+) -> IResult<'_, Vec<T>> {
+    separated_list1(space1, signed).parse(input)
+}
+
+/// Numbers separated by `sep`, e.g. `75,47,61`
+/// with `","`.
+pub fn numbers_by<'a, T: FromStr>(
+    sep: &'static str,
+) -> impl Parser<&'a str, Output = Vec<T>, Error = Error<'a>>
+{
+    separated_list1(tag(sep), signed)
+}
+
+/// One `item` per line.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = Error<'a>>
+{
+    separated_list1(line_ending, item)
+}
+
+/// The empty line between two sections.
+pub fn blank_line(input: &str) -> IResult<'_, ()> {
+    (line_ending, line_ending).map(|_| ()).parse(input)
+}
+
+/// Sections of the same shape, separated by
+/// empty lines. Sections of different shapes
+/// are `separated_pair(a, blank_line, b)`.
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = Error<'a>>
+{
+    separated_list1(blank_line, section)
+}
+
+/// `value` after a `key:` label, e.g.
+/// `Register A: 729` with `"Register A"`.
+pub fn key_value<'a, O>(
+    key: &'static str,
+    value: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> impl Parser<&'a str, Output = O, Error = Error<'a>> {
+    preceded((tag(key), tag(":"), space0), value)
+}
+
+/// The number in a `label N:` prefix, e.g. `13`
+/// in `Game 13: 7 blue` with `"Game"`, along
+/// with the spaces after it.
+pub fn id_prefix<'a, T: FromStr>(
+    label: &'static str,
+) -> impl Parser<&'a str, Output = T, Error = Error<'a>> {
+    delimited(
+        (tag(label), space1),
+        number,
+        (tag(":"), space0),
+    )
+}
+
+/// Lines of characters up to an empty line or
+/// the end of the input, every one as wide as
+/// the first.
+pub fn grid(input: &str) -> IResult<'_, Grid<char>> {
+    let (rest, rows) =
+        lines(take_till1(|c| c == '\n' || c == '\r'))
+            .parse(input)?;
+    let width = rows[0].chars().count();
+    if let Some(row) =
+        rows.iter().find(|row| row.chars().count() != width)
+    {
+        // Past the first row this is a grid, just a
+        // broken one.
+        return Err(nom::Err::Failure(Error {
+            input: row,
+            expected: Expected::Width(width),
+            context: vec![],
+        }));
+    }
+    let cells =
+        rows.iter().flat_map(|row| row.chars()).collect();
+    Ok((rest, Grid::new(width, cells)))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        branch::alt,
+        character::complete::{char, u32},
+        combinator::value,
+        error::context,
+        sequence::{delimited, separated_pair},
+    };
+    use rstest::rstest;
+
+    use super::*;
+
+    fn pair(input: &str) -> IResult<'_, (u32, u32)> {
+        context(
+            "pair",
+            delimited(
+                tag("("),
+                separated_pair(u32, char(','), u32),
+                char(')'),
+            ),
+        )
+        .parse(input)
+    }
+
+    fn unwrap_error(err: nom::Err<Error<'_>>) -> Error<'_> {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                panic!(
+                    "complete parsers are never incomplete"
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_tag() {
+        let input = "[1,2)";
+        let err = unwrap_error(pair(input).unwrap_err());
+        assert_eq!(Expected::Tag("("), err.expected);
+        assert_eq!(0, err.offset(input));
+        assert_eq!(vec![(input, "pair")], err.context);
+    }
+
+    #[test]
+    fn test_expected_char() {
+        let input = "(1;2)";
+        let err = unwrap_error(pair(input).unwrap_err());
+        assert_eq!(Expected::Char(','), err.expected);
+        assert_eq!(2, err.offset(input));
+    }
+
+    #[test]
+    fn test_expected_number() {
+        let input = "(1,x)";
+        let err = unwrap_error(pair(input).unwrap_err());
+        assert_eq!("a number", err.expected.to_string());
+        assert_eq!(3, err.offset(input));
+    }
+
+    #[test]
+    fn test_alt_reports_all_alternatives() {
+        let input = "purple";
+        let err = unwrap_error(
+            alt((tag("red"), tag("green"), tag("blue")))
+                .parse(input)
+                .unwrap_err(),
+        );
+        assert_eq!(
+            "one of `red`, `green`, `blue`",
+            err.expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_alt_reports_furthest_branch() {
+        let input = "(1,2]";
+        let err = unwrap_error(
+            alt((pair, value((0, 0), tag("()"))))
+                .parse(input)
+                .unwrap_err(),
+        );
+        assert_eq!(Expected::Char(')'), err.expected);
+        assert_eq!(4, err.offset(input));
+    }
+
+    #[rstest]
+    #[case("143", 143)]
+    #[case("-7", -7)]
+    #[case("+3", 3)]
+    fn test_signed(
+        #[case] input: &str,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(
+            Ok(("", expected)),
+            signed::<i64>(input)
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Ok((" 2", 1_u8)), number::<u8>("1 2"));
+        let err =
+            unwrap_error(number::<u8>("-1").unwrap_err());
+        assert_eq!("a number", err.expected.to_string());
+        let err =
+            unwrap_error(number::<u8>("256").unwrap_err());
+        assert_eq!(
+            "a number that fits in `u8`",
+            err.expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            Ok(("", vec![3, 4])),
+            numbers::<u32>("3   4")
+        );
+        assert_eq!(
+            Ok(("\n1", vec![7, -6, 4])),
+            numbers::<i32>("7 -6\t4\n1")
+        );
+        assert_eq!(
+            Ok(("", vec![75, 47, 61])),
+            numbers_by::<u32>(",").parse("75,47,61")
+        );
+    }
+
+    #[rstest]
+    #[case::lf("\n")]
+    #[case::crlf("\r\n")]
+    fn test_sections(
+        #[case] nl: &str,
+    ) -> miette::Result<()> {
+        let input = format!(
+            "47|53{nl}97|13{nl}{nl}75,47{nl}61{nl}"
+        );
+        let rules = lines(separated_pair(
+            number::<u32>,
+            tag("|"),
+            number,
+        ));
+        let updates = lines(numbers_by::<u32>(","));
+        assert_eq!(
+            (
+                vec![(47, 53), (97, 13)],
+                vec![vec![75, 47], vec![61]]
+            ),
+            parse_all(
+                &input,
+                separated_pair(rules, blank_line, updates)
+            )?
+        );
+        assert_eq!(
+            vec![vec![1, 2], vec![3]],
+            parse_all(
+                &format!("1{nl}2{nl}{nl}3"),
+                sections(lines(number::<u32>))
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_all_rejects_leftovers() {
+        let err = parse_all("1 2\nx", numbers::<u32>)
+            .unwrap_err();
+        assert_eq!(
+            "Parse error at line 2, column 1",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(
+            Ok(("7 blue", 13)),
+            id_prefix::<u32>("Game")
+                .parse("Game 13: 7 blue")
+        );
+        assert_eq!(
+            Ok(("", -729)),
+            key_value("Register A", signed::<i32>)
+                .parse("Register A: -729")
+        );
+    }
+
+    #[rstest]
+    #[case::lf("\n")]
+    #[case::crlf("\r\n")]
+    fn test_grid(#[case] nl: &str) {
+        let input = format!("ab{nl}cd{nl}{nl}rest");
+        let (rest, grid) = grid(&input).unwrap();
+        assert_eq!(format!("{nl}{nl}rest"), rest);
+        assert_eq!("ab\ncd\n", grid.to_string());
+    }
+
+    #[test]
+    fn test_ragged_grid() {
+        let input = "abc\nde\nfgh";
+        let err = unwrap_error(grid(input).unwrap_err());
+        assert_eq!(Expected::Width(3), err.expected);
+        assert_eq!(4, err.offset(input));
+    }
+
+    #[rstest]
+    #[case::lf("\n")]
+    #[case::crlf("\r\n")]
+    fn test_each_line(
+        #[case] nl: &str,
+    ) -> miette::Result<()> {
+        let input = format!("1 2{nl}3{nl}{nl}");
+        assert_eq!(
+            vec![vec![1, 2], vec![3]],
+            each_line(&input, numbers::<u32>)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_each_line_reports_every_bad_line() {
+        let input = "1 2\n3 x\n4\n5,6\n";
+        let err =
+            each_line(input, numbers::<u32>).unwrap_err();
+        assert_eq!(
+            "Parse errors at line 2, column 2; line 4, column 2",
+            err.to_string()
+        );
+        let AocError::ParseErrors { labels, .. } = err
+        else {
+            panic!("expected several parse errors");
+        };
+        assert_eq!(
+            vec![(5, 1), (11, 1)],
+            labels
+                .iter()
+                .map(|label| (label.offset(), label.len()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            1,
+            labels.iter().filter(|l| l.primary()).count()
+        );
+    }
+
+    #[test]
+    fn test_each_line_with_one_bad_line() {
+        let err =
+            each_line("1\nx", numbers::<u32>).unwrap_err();
+        assert_eq!(
+            "Parse error at line 2, column 1",
+            err.to_string()
+        );
+    }
+}
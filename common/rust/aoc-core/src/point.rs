@@ -0,0 +1,147 @@
+//! Positions and steps on a grid.
+//!
+//! `x` counts columns to the right and `y` rows
+//! down, the way puzzle maps are read, so
+//! [`Direction::N`] is a step of `(0, -1)`.
+//! Distances come with glam:
+//! [`IVec2::manhattan_distance`] and
+//! [`IVec2::chebyshev_distance`].
+
+pub use glam::IVec2;
+
+/// A position, or the difference between two.
+pub type Point = IVec2;
+
+/// One of the eight compass directions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 8] =
+        [N, NE, E, SE, S, SW, W, NW];
+    /// Up, right, down and left.
+    pub const ORTHOGONAL: [Direction; 4] = [N, E, S, W];
+    pub const DIAGONAL: [Direction; 4] = [NE, SE, SW, NW];
+
+    /// A single step this way.
+    pub const fn step(self) -> Point {
+        let (x, y) = match self {
+            N => (0, -1),
+            NE => (1, -1),
+            E => (1, 0),
+            SE => (1, 1),
+            S => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (-1, -1),
+        };
+        IVec2::new(x, y)
+    }
+
+    /// The direction `eighths` eighths of a turn
+    /// clockwise from this one.
+    const fn turn(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// A quarter turn clockwise.
+    pub const fn turn_right(self) -> Direction {
+        self.turn(2)
+    }
+
+    /// A quarter turn anticlockwise.
+    pub const fn turn_left(self) -> Direction {
+        self.turn(6)
+    }
+
+    pub const fn reverse(self) -> Direction {
+        self.turn(4)
+    }
+
+    /// Points one, two, three... steps from
+    /// `from` this way, without `from` itself;
+    /// `take(k)` for the first `k`.
+    pub fn ray(
+        self,
+        from: Point,
+    ) -> impl Iterator<Item = Point> {
+        let step = self.step();
+        std::iter::successors(
+            Some(from + step),
+            move |at| Some(at + step),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(N, E, W, S)]
+    #[case(E, S, N, W)]
+    #[case(NE, SE, NW, SW)]
+    #[case(NW, NE, SW, SE)]
+    fn test_turns(
+        #[case] dir: Direction,
+        #[case] right: Direction,
+        #[case] left: Direction,
+        #[case] reverse: Direction,
+    ) {
+        assert_eq!(right, dir.turn_right());
+        assert_eq!(left, dir.turn_left());
+        assert_eq!(reverse, dir.reverse());
+        assert_eq!(-dir.step(), dir.reverse().step());
+    }
+
+    #[test]
+    fn test_steps() {
+        assert_eq!(IVec2::new(0, -1), N.step());
+        assert_eq!(IVec2::new(1, 1), SE.step());
+        for dir in Direction::ORTHOGONAL {
+            assert_eq!(1, dir.step().abs().element_sum());
+        }
+        assert!(
+            Direction::DIAGONAL
+                .iter()
+                .all(|dir| dir.step().abs() == IVec2::ONE)
+        );
+    }
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(
+            vec![
+                IVec2::new(3, 1),
+                IVec2::new(4, 0),
+                IVec2::new(5, -1)
+            ],
+            NE.ray(IVec2::new(2, 2))
+                .take(3)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (IVec2::new(1, 2), IVec2::new(4, -2));
+        assert_eq!(7, a.manhattan_distance(b));
+        assert_eq!(4, a.chebyshev_distance(b));
+    }
+}
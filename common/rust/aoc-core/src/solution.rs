@@ -0,0 +1,341 @@
+use std::{fmt, str::FromStr};
+
+use crate::Answer;
+
+/// Which half of a day's puzzle a solution
+/// answers.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_start_matches("part") {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!(
+                "part `{s}` must be either `1` or `2`"
+            )),
+        }
+    }
+}
+
+/// A single part of a single day, with enough
+/// metadata to find it among all the other ones.
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn part(&self) -> Part;
+    fn process(
+        &self,
+        input: &str,
+    ) -> miette::Result<Answer>;
+
+    /// Directory of the crate the solution lives
+    /// in, where older `input1.txt`/`input2.txt`
+    /// files may still be kept.
+    fn dir(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl fmt::Debug for dyn Solution + '_ {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl fmt::Display for dyn Solution + '_ {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} day {:02} part {}",
+            self.year(),
+            self.day(),
+            self.part()
+        )
+    }
+}
+
+/// Plain `partN::process` function registered as
+/// a [`Solution`]. This is what [`register!`]
+/// produces, converting whatever the function
+/// returns into an [`Answer`].
+///
+/// [`register!`]: crate::register
+pub struct Process {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub process: fn(&str) -> miette::Result<Answer>,
+    pub dir: &'static str,
+}
+
+impl Solution for Process {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn part(&self) -> Part {
+        self.part
+    }
+
+    fn process(
+        &self,
+        input: &str,
+    ) -> miette::Result<Answer> {
+        (self.process)(input)
+    }
+
+    fn dir(&self) -> Option<&'static str> {
+        Some(self.dir)
+    }
+}
+
+/// Entry collected by [`inventory`] from every
+/// linked day crate.
+pub struct Registration(pub &'static dyn Solution);
+
+inventory::collect!(Registration);
+
+/// Registers a day's `process` functions so they
+/// show up in [`Registry::collect`]. They can
+/// return anything that turns into an [`Answer`],
+/// e.g. a `u32`.
+///
+/// ```ignore
+/// aoc_core::register! {
+///     year: 2024,
+///     day: 1,
+///     part1: part1::process,
+///     part2: part2::process,
+/// }
+/// ```
+#[macro_export]
+macro_rules! register {
+    (
+        year: $year:literal,
+        day: $day:literal,
+        $($part:ident: $process:path),+ $(,)?
+    ) => {
+        $(
+            $crate::inventory::submit! {
+                $crate::solution::Registration(
+                    &$crate::solution::Process {
+                        year: $year,
+                        day: $day,
+                        part: $crate::register!(@part $part),
+                        process: |input| {
+                            $process(input).map(::core::convert::Into::into)
+                        },
+                        dir: env!("CARGO_MANIFEST_DIR"),
+                    },
+                )
+            }
+        )+
+    };
+    (@part part1) => { $crate::solution::Part::One };
+    (@part part2) => { $crate::solution::Part::Two };
+}
+
+/// Every registered solution, ordered by year,
+/// day and part.
+#[derive(Debug)]
+pub struct Registry {
+    solutions: Vec<&'static dyn Solution>,
+}
+
+impl Registry {
+    /// Collects the solutions of every day crate
+    /// linked into the current binary.
+    pub fn collect() -> Self {
+        inventory::iter::<Registration>
+            .into_iter()
+            .map(|r| r.0)
+            .collect()
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = &'static dyn Solution> + '_
+    {
+        self.solutions.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// Years that have at least one solution.
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> =
+            self.iter().map(|s| s.year()).collect();
+        years.dedup();
+        years
+    }
+
+    /// Solutions matching the given filters;
+    /// `None` matches anything.
+    pub fn select(
+        &self,
+        year: Option<u16>,
+        day: Option<u8>,
+        part: Option<Part>,
+    ) -> impl Iterator<Item = &'static dyn Solution> + '_
+    {
+        self.iter().filter(move |s| {
+            year.is_none_or(|y| s.year() == y)
+                && day.is_none_or(|d| s.day() == d)
+                && part.is_none_or(|p| s.part() == p)
+        })
+    }
+
+    pub fn get(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+    ) -> Option<&'static dyn Solution> {
+        self.select(Some(year), Some(day), Some(part))
+            .next()
+    }
+}
+
+impl FromIterator<&'static dyn Solution> for Registry {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = &'static dyn Solution>,
+    {
+        let mut solutions: Vec<_> =
+            iter.into_iter().collect();
+        solutions
+            .sort_by_key(|s| (s.year(), s.day(), s.part()));
+        Registry { solutions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(input: &str) -> miette::Result<usize> {
+        Ok(input.len())
+    }
+
+    crate::register! {
+        year: 1999,
+        day: 2,
+        part2: answer,
+        part1: answer,
+    }
+
+    crate::register! {
+        year: 1999,
+        day: 1,
+        part1: answer,
+    }
+
+    #[test]
+    fn test_collect() {
+        let registry = Registry::collect();
+        let ids: Vec<String> = registry
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "1999 day 01 part 1",
+                "1999 day 02 part 1",
+                "1999 day 02 part 2",
+            ],
+            ids
+        );
+        assert_eq!(vec![1999], registry.years());
+    }
+
+    #[test]
+    fn test_get() -> miette::Result<()> {
+        let registry = Registry::collect();
+        let solution = registry
+            .get(1999, 2, Part::Two)
+            .expect("registered above");
+        assert_eq!(
+            Answer::Int(3),
+            solution.process("abc")?
+        );
+        assert!(registry.get(1999, 1, Part::Two).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() {
+        let registry = Registry::collect();
+        assert_eq!(
+            3,
+            registry.select(None, None, None).count()
+        );
+        assert_eq!(
+            2,
+            registry
+                .select(Some(1999), Some(2), None)
+                .count()
+        );
+        assert_eq!(
+            2,
+            registry
+                .select(None, None, Some(Part::One))
+                .count()
+        );
+        assert_eq!(
+            0,
+            registry.select(Some(2000), None, None).count()
+        );
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), "part2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Turns the files in a day's `examples/`
+//! directory into rstest cases, so adding a
+//! regression test is a matter of dropping in a
+//! file. See [`aoc_core::examples`] for the
+//! layout.
+//!
+//! Each day crate has a `build.rs` with
+//!
+//! ```ignore
+//! fn main() {
+//!     aoc_test::build();
+//! }
+//! ```
+//!
+//! and pulls the generated tests into its
+//! `lib.rs` with
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! aoc_test::examples!();
+//! ```
+
+use std::{fmt::Write, fs, io, path::Path};
+
+use aoc_core::{
+    Part,
+    examples::{self, Example},
+};
+
+/// Name of the file [`build`] writes into
+/// `OUT_DIR`.
+pub const GENERATED: &str = "aoc-examples.rs";
+
+/// Generates the example tests of the crate being
+/// built. Meant to be called from `build.rs`.
+pub fn build() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .expect("called from a build script");
+    let out_dir = std::env::var("OUT_DIR")
+        .expect("called from a build script");
+    let dir = Path::new(&manifest_dir).join("examples");
+
+    // Watching the whole crate until there is an
+    // `examples/` picks it up when it's created.
+    if dir.is_dir() {
+        println!("cargo::rerun-if-changed=examples");
+    } else {
+        println!("cargo::rerun-if-changed=.");
+    }
+
+    let code = generate(&dir).unwrap_or_else(|e| {
+        panic!("Failed to read {}: {e}", dir.display())
+    });
+    fs::write(
+        Path::new(&out_dir).join(GENERATED),
+        code,
+    )
+    .expect("Failed to write the example tests");
+}
+
+/// Source of an rstest function per part, with a
+/// case for every example that has an answer for
+/// it. Both sides are compared as
+/// [`aoc_core::Answer`]s.
+pub fn generate(dir: &Path) -> io::Result<String> {
+    let examples = examples::load_dir(dir)?;
+
+    let mut code = String::new();
+    for part in Part::ALL {
+        let cases: Vec<&Example> = examples
+            .iter()
+            .filter(|example| {
+                example.answer(part).is_some()
+            })
+            .collect();
+        if cases.is_empty() {
+            continue;
+        }
+
+        code.push_str("#[rstest::rstest]\n");
+        for example in cases {
+            writeln!(
+                code,
+                "#[case::{}({:?}, {:?})]",
+                identifier(&example.name),
+                example.input,
+                example.answer(part).unwrap_or_default(),
+            )
+            .expect("writing to a String");
+        }
+        writeln!(
+            code,
+            "fn part{part}(#[case] input: &str, #[case] expected: &str) \
+             -> miette::Result<()> {{\n    \
+             assert_eq!(\n        \
+             aoc_core::Answer::from(expected),\n        \
+             aoc_core::Answer::from(crate::part{part}::process(input)?)\n    \
+             );\n    \
+             Ok(())\n}}\n"
+        )
+        .expect("writing to a String");
+    }
+    Ok(code)
+}
+
+/// Turns an example's file name into something
+/// usable as an rstest case name.
+fn identifier(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() { c } else { '_' }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("example_{name}")
+    } else {
+        name
+    }
+}
+
+/// Includes the tests generated by [`build`].
+#[macro_export]
+macro_rules! examples {
+    () => {
+        mod examples {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/aoc-examples.rs"
+            ));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("example-1.txt"),
+            "3   4\n",
+        )?;
+        fs::write(
+            dir.path().join("example-1.part1.answer"),
+            "11\n",
+        )?;
+        fs::write(
+            dir.path().join("2.txt"),
+            "---\npart1: 4\npart2: 9\n---\nX\n",
+        )?;
+
+        let code = generate(dir.path())?;
+        assert_eq!(
+            "#[rstest::rstest]\n\
+             #[case::example_2(\"X\\n\", \"4\")]\n\
+             #[case::example_1(\"3   4\\n\", \"11\")]\n\
+             fn part1(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {\n    \
+             assert_eq!(\n        \
+             aoc_core::Answer::from(expected),\n        \
+             aoc_core::Answer::from(crate::part1::process(input)?)\n    \
+             );\n    \
+             Ok(())\n\
+             }\n\
+             \n\
+             #[rstest::rstest]\n\
+             #[case::example_2(\"X\\n\", \"9\")]\n\
+             fn part2(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {\n    \
+             assert_eq!(\n        \
+             aoc_core::Answer::from(expected),\n        \
+             aoc_core::Answer::from(crate::part2::process(input)?)\n    \
+             );\n    \
+             Ok(())\n\
+             }\n\
+             \n",
+            code
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_without_examples() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(
+            "",
+            generate(&dir.path().join("examples"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!("example_1", identifier("example-1"));
+        assert_eq!("example_01", identifier("01"));
+        assert_eq!(
+            "larger_grid",
+            identifier("larger grid")
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::{Part, input};
+use miette::{
+    IntoDiagnostic, LabeledSpan, MietteDiagnostic,
+    NamedSource, Report,
+};
+use serde::{Deserialize, Serialize};
+
+/// Written at the top of the file whenever it's
+/// saved.
+const HEADER: &str = "\
+# Correct answers for the inputs in `inputs/`,
+# checked by `aoc verify`.
+";
+
+/// Known correct answers, committed as
+/// `answers.toml` at the repository root:
+///
+/// ```toml
+/// [2024.day-05]
+/// part1 = "143"
+/// part2 = "123"
+/// ```
+#[derive(
+    Debug, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Answers {
+    years: BTreeMap<String, BTreeMap<String, Day>>,
+}
+
+#[derive(
+    Debug, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(deny_unknown_fields)]
+struct Day {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+impl Day {
+    fn part(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+/// `answers.toml` at the root of the repository.
+pub fn default_path() -> PathBuf {
+    input::repo_root().join("answers.toml")
+}
+
+fn day_key(day: u8) -> String {
+    format!("day-{day:02}")
+}
+
+impl Answers {
+    /// Reads the answers at `path`, which is fine
+    /// to not exist yet.
+    pub fn load(path: &Path) -> miette::Result<Answers> {
+        let src = match fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e)
+                if e.kind() == io::ErrorKind::NotFound =>
+            {
+                return Ok(Answers::default());
+            }
+            Err(e) => return Err(e).into_diagnostic(),
+        };
+        Answers::parse(&path.display().to_string(), src)
+    }
+
+    fn parse(
+        name: &str,
+        src: String,
+    ) -> miette::Result<Answers> {
+        toml::from_str(&src).map_err(|e| {
+            let mut diagnostic = MietteDiagnostic::new(
+                "Invalid answers file",
+            )
+            .with_code("aoc::invalid_answers");
+            if let Some(span) = e.span() {
+                diagnostic = diagnostic.with_label(
+                    LabeledSpan::at(span, e.message()),
+                );
+            }
+            Report::new(diagnostic).with_source_code(
+                NamedSource::new(name, src),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let body =
+            toml::to_string(self).into_diagnostic()?;
+        fs::write(path, format!("{HEADER}\n{body}"))
+            .into_diagnostic()
+    }
+
+    pub fn get(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+    ) -> Option<&str> {
+        let day = self
+            .years
+            .get(&year.to_string())?
+            .get(&day_key(day))?;
+        match part {
+            Part::One => day.part1.as_deref(),
+            Part::Two => day.part2.as_deref(),
+        }
+    }
+
+    pub fn set(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) {
+        *self
+            .years
+            .entry(year.to_string())
+            .or_default()
+            .entry(day_key(day))
+            .or_default()
+            .part(part) = Some(answer.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let answers = Answers::parse(
+            "answers.toml",
+            "[2024.day-05]\npart1 = \"143\"\n".to_string(),
+        )?;
+        assert_eq!(
+            Some("143"),
+            answers.get(2024, 5, Part::One)
+        );
+        assert_eq!(None, answers.get(2024, 5, Part::Two));
+        assert_eq!(None, answers.get(2023, 5, Part::One));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_points_at_mistakes() {
+        let report = Answers::parse(
+            "answers.toml",
+            "[2024.day-05]\npart3 = \"1\"\n".to_string(),
+        )
+        .unwrap_err();
+        let labels: Vec<_> =
+            report.labels().into_iter().flatten().collect();
+        assert_eq!(1, labels.len());
+        assert_eq!(14, labels[0].offset());
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.toml");
+        assert_eq!(
+            Answers::default(),
+            Answers::load(&path)?
+        );
+
+        let mut answers = Answers::default();
+        answers.set(2024, 5, Part::Two, "123");
+        answers.set(2024, 5, Part::One, "143");
+        answers.set(2023, 1, Part::One, "142");
+        answers.save(&path)?;
+
+        assert_eq!(
+            format!(
+                "{HEADER}\n\
+                 [2023.day-01]\n\
+                 part1 = \"142\"\n\
+                 \n\
+                 [2024.day-05]\n\
+                 part1 = \"143\"\n\
+                 part2 = \"123\"\n"
+            ),
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(answers, Answers::load(&path)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+//! Links every day crate into the binary. Nothing
+//! is used directly: the crates register their
+//! solutions with [`aoc_core::register!`] as soon
+//! as they are linked.
+
+use aoc_2023_day_01 as _;
+use aoc_2023_day_02 as _;
+use aoc_2023_day_03 as _;
+use aoc_2024_day_01 as _;
+use aoc_2024_day_02 as _;
+use aoc_2024_day_03 as _;
+use aoc_2024_day_04 as _;
+use aoc_2024_day_05 as _;
@@ -0,0 +1,188 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use aoc_core::Part;
+use fetch_input::leaderboard::{Leaderboard, Member};
+
+use crate::{table, wait};
+
+/// Members by score, with how much each gained
+/// since `previous` and how many stars they have.
+pub fn standings(
+    leaderboard: &Leaderboard,
+    previous: Option<&Leaderboard>,
+) -> String {
+    let rows: Vec<[String; 5]> = leaderboard
+        .standings()
+        .into_iter()
+        .enumerate()
+        .map(|(rank, member)| {
+            let before = previous.map(|previous| {
+                previous
+                    .members
+                    .get(&member.id.to_string())
+                    .map_or(0, |member| member.local_score)
+            });
+            let change = match before {
+                Some(before)
+                    if member.local_score > before =>
+                {
+                    format!(
+                        "+{}",
+                        member.local_score - before
+                    )
+                }
+                _ => String::new(),
+            };
+            [
+                format!("{})", rank + 1),
+                member.display_name(),
+                member.local_score.to_string(),
+                change,
+                member.stars.to_string(),
+            ]
+        })
+        .collect();
+    table::render(
+        ["#", "name", "score", "change", "stars"],
+        &rows,
+    )
+}
+
+/// How long after the puzzle unlocked a member
+/// got a star.
+fn star_time(
+    member: &Member,
+    year: u16,
+    day: u8,
+    part: Part,
+) -> Option<Duration> {
+    let at = UNIX_EPOCH
+        + Duration::from_secs(member.star(day, part)?);
+    at.duration_since(wait::unlock_time(year, day)).ok()
+}
+
+/// Everyone with a star on `day`, with how long
+/// each part took them and the points they got
+/// for it, best first.
+pub fn day(
+    leaderboard: &Leaderboard,
+    year: u16,
+    day: u8,
+) -> String {
+    let points = leaderboard.day_points(day);
+    let mut members: Vec<_> = leaderboard
+        .members
+        .values()
+        .filter_map(|member| {
+            let part1 =
+                star_time(member, year, day, Part::One)?;
+            let part2 =
+                star_time(member, year, day, Part::Two);
+            Some((member, part1, part2, points[&member.id]))
+        })
+        .collect();
+    members.sort_by_key(|&(member, .., points)| {
+        (
+            std::cmp::Reverse(points),
+            member.display_name(),
+        )
+    });
+
+    let rows: Vec<[String; 4]> = members
+        .into_iter()
+        .map(|(member, part1, part2, points)| {
+            [
+                member.display_name(),
+                wait::format_duration(part1),
+                part2
+                    .map(wait::format_duration)
+                    .unwrap_or_default(),
+                format!("+{points}"),
+            ]
+        })
+        .collect();
+    format!(
+        "Day {day:02}\n{}",
+        table::render(
+            ["name", "part 1", "part 2", "points"],
+            &rows
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = include_str!(
+        "../../tools/fetch-input/tests/fixtures/leaderboard.json"
+    );
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard::parse("leaderboard.json", JSON)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_standings() {
+        assert_eq!(
+            "\
+#   name                   score  change  stars
+--  ---------------------  -----  ------  -----
+1)  Alice                  15             4
+2)  Bob                    10             3
+3)  (anonymous user #303)  2              1
+4)  Dave                   0              0
+",
+            standings(&leaderboard(), None)
+        );
+    }
+
+    #[test]
+    fn test_standings_changes() {
+        let leaderboard = leaderboard();
+        let mut previous = leaderboard.clone();
+        previous
+            .members
+            .get_mut("101")
+            .unwrap()
+            .local_score = 11;
+        previous.members.remove("303");
+        assert_eq!(
+            "\
+#   name                   score  change  stars
+--  ---------------------  -----  ------  -----
+1)  Alice                  15     +4      4
+2)  Bob                    10             3
+3)  (anonymous user #303)  2      +2      1
+4)  Dave                   0              0
+",
+            standings(&leaderboard, Some(&previous))
+        );
+    }
+
+    #[test]
+    fn test_day() {
+        assert_eq!(
+            "\
+Day 01
+name                   part 1          part 2    points
+---------------------  --------------  --------  ------
+Alice                  00:05:00        00:10:00  +8
+Bob                    00:07:00        00:33:20  +6
+(anonymous user #303)  1 day 01:00:00            +2
+",
+            day(&leaderboard(), 2024, 1)
+        );
+        assert_eq!(
+            "\
+Day 02
+name   part 1    part 2    points
+-----  --------  --------  ------
+Alice  00:10:00  00:20:00  +7
+Bob    00:08:20            +4
+",
+            day(&leaderboard(), 2024, 2)
+        );
+    }
+}
@@ -0,0 +1,492 @@
+mod answers;
+mod days;
+mod leaderboard;
+mod run;
+mod stats;
+mod submit;
+mod table;
+mod verify;
+mod wait;
+
+use std::{io, path::PathBuf, time::SystemTime};
+
+use answers::Answers;
+use aoc_core::{
+    Answer, Part, Registry,
+    input::{self, Source},
+};
+use clap::{Parser, Subcommand};
+use fetch_input::{
+    cli::{Site, parse_day},
+    config::{self, Config},
+    journal::{self, Journal},
+    submit::Verdict,
+};
+use miette::{IntoDiagnostic, Report, miette};
+use verify::Status;
+use wait::{Clock, SystemClock};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs one part, one day, a whole year or
+    /// everything, depending on how much is
+    /// given.
+    Run {
+        year: Option<u16>,
+        /// Either "5", "05" or "day-05".
+        #[clap(value_parser = parse_day)]
+        day: Option<u8>,
+        /// Either "1" or "part1".
+        part: Option<Part>,
+        /// Directory holding
+        /// `{year}/day-{day}.txt` inputs.
+        /// Defaults to `$AOC_INPUTS` or
+        /// `inputs/` at the repository root.
+        #[clap(long, value_name = "DIR")]
+        inputs: Option<PathBuf>,
+        /// Reads the input of a single day from
+        /// this file instead.
+        #[clap(
+            long,
+            value_name = "PATH",
+            requires = "day",
+            conflicts_with_all = ["inputs", "stdin"]
+        )]
+        input: Option<PathBuf>,
+        /// Reads the input of a single day from
+        /// stdin instead.
+        #[clap(
+            long,
+            requires = "day",
+            conflicts_with = "inputs"
+        )]
+        stdin: bool,
+    },
+    /// Runs a part's solution and submits its
+    /// answer, refusing answers that earlier
+    /// attempts already ruled out.
+    Submit {
+        year: u16,
+        /// Either "5", "05" or "day-05".
+        #[clap(value_parser = parse_day)]
+        day: u8,
+        /// Either "1" or "part1".
+        part: Part,
+        /// Submits this instead of running the
+        /// solution.
+        #[clap(long)]
+        answer: Option<String>,
+        /// Directory holding the inputs and the
+        /// history of submitted answers.
+        /// Defaults to `$AOC_INPUTS` or `inputs/`
+        /// at the repository root.
+        #[clap(long, value_name = "DIR")]
+        inputs: Option<PathBuf>,
+        /// Where an accepted answer is recorded.
+        /// Defaults to `answers.toml` at the
+        /// repository root.
+        #[clap(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+        /// Submits even if the history says the
+        /// answer can't be right.
+        #[clap(long)]
+        force: bool,
+        #[clap(flatten)]
+        site: Site,
+    },
+    /// Checks solutions against the answers
+    /// recorded in `answers.toml`, failing if any
+    /// of them changed.
+    Verify {
+        year: Option<u16>,
+        /// Either "5", "05" or "day-05".
+        #[clap(value_parser = parse_day)]
+        day: Option<u8>,
+        /// Either "1" or "part1".
+        part: Option<Part>,
+        /// Directory holding
+        /// `{year}/day-{day}.txt` inputs.
+        /// Defaults to `$AOC_INPUTS` or
+        /// `inputs/` at the repository root.
+        #[clap(long, value_name = "DIR")]
+        inputs: Option<PathBuf>,
+        /// Defaults to `answers.toml` at the
+        /// repository root.
+        #[clap(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+        /// Records the answers of solutions that
+        /// don't have one yet.
+        #[clap(long)]
+        record: bool,
+    },
+    /// Counts down to a puzzle's unlock, then
+    /// downloads its input and description and
+    /// creates its crate.
+    Wait {
+        /// Defaults to the year of the next
+        /// puzzle to unlock.
+        #[clap(long, requires = "day")]
+        year: Option<u16>,
+        /// Either "5", "05" or "day-05". Defaults
+        /// to the next puzzle to unlock.
+        #[clap(long, value_parser = parse_day)]
+        day: Option<u8>,
+        /// Directory holding
+        /// `{year}/day-{day}.txt` inputs.
+        /// Defaults to `$AOC_INPUTS` or
+        /// `inputs/` at the repository root.
+        #[clap(long, value_name = "DIR")]
+        inputs: Option<PathBuf>,
+        #[clap(flatten)]
+        site: Site,
+    },
+    /// Shows a private leaderboard: the standings
+    /// and how long everyone took for each day.
+    /// Downloaded at most every 15 minutes.
+    Leaderboard {
+        year: u16,
+        /// Only shows this day's times. Either
+        /// "5", "05" or "day-05".
+        #[clap(long, value_parser = parse_day)]
+        day: Option<u8>,
+        /// The number at the end of the
+        /// leaderboard's URL. Defaults to the
+        /// config file's `leaderboard`.
+        #[clap(long, env = "AOC_LEADERBOARD")]
+        id: Option<u64>,
+        #[clap(flatten)]
+        site: Site,
+    },
+    /// Shows how long each part took from
+    /// fetching the input to solving it, as
+    /// kept in the journal in the inputs
+    /// directory.
+    Stats {
+        year: Option<u16>,
+        /// Directory holding `journal.toml`.
+        /// Defaults to `$AOC_INPUTS` or `inputs/`
+        /// at the repository root.
+        #[clap(long, value_name = "DIR")]
+        inputs: Option<PathBuf>,
+        /// Prints JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Lists every registered solution.
+    List,
+    /// Adds a day's crate to this runner, as
+    /// `wait` does for the crates it creates.
+    Register {
+        year: u16,
+        /// Either "5", "05" or "day-05".
+        #[clap(value_parser = parse_day)]
+        day: u8,
+    },
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+    let registry = Registry::collect();
+
+    match args.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            inputs,
+            input,
+            stdin,
+        } => {
+            let source = match (inputs, input, stdin) {
+                (_, _, true) => Source::Stdin,
+                (_, Some(file), _) => Source::File(file),
+                (Some(dir), ..) => Source::Dir(dir),
+                (None, None, false) => Source::default(),
+            };
+            run::run(&registry, &source, year, day, part)
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            inputs,
+            answers,
+            force,
+            site,
+        } => {
+            let inputs =
+                inputs.unwrap_or_else(input::default_dir);
+            let answer = match answer {
+                Some(answer) => Answer::parse(&answer),
+                None => {
+                    let solution = registry
+                        .get(year, day, part)
+                        .ok_or_else(|| {
+                            miette!(
+                                "No solution registered for {year} day {day:02} part {part}"
+                            )
+                        })?;
+                    let input = Source::Dir(inputs.clone())
+                        .load(solution)?;
+                    let (answer, elapsed) =
+                        run::solve(solution, &input);
+                    let answer = answer?;
+                    println!(
+                        "{solution}: {answer} ({elapsed:.2?})"
+                    );
+                    answer
+                }
+            };
+            let answer = submit::text(&answer)?;
+
+            let (client, origin) = site.connect()?;
+            println!(
+                "Submitting `{answer}` with session {} from {origin}",
+                client.session()
+            );
+            let verdict = submit::submit(
+                &client, &inputs, year, day, part, &answer,
+                force,
+            )?;
+            println!("{verdict}");
+            if verdict != Verdict::Correct {
+                return Err(miette!(
+                    "The answer wasn't accepted"
+                ));
+            }
+
+            let path = answers
+                .unwrap_or_else(answers::default_path);
+            let mut answers = Answers::load(&path)?;
+            answers.set(year, day, part, &answer);
+            answers.save(&path)?;
+            println!("Recorded in {}", path.display());
+            Ok(())
+        }
+        Command::Verify {
+            year,
+            day,
+            part,
+            inputs,
+            answers,
+            record,
+        } => {
+            let inputs =
+                inputs.unwrap_or_else(input::default_dir);
+            let source = Source::Dir(inputs.clone());
+            let path = answers
+                .unwrap_or_else(answers::default_path);
+            let mut answers = Answers::load(&path)?;
+
+            let checks = verify::verify(
+                &registry, &source, &answers, year, day,
+                part,
+            );
+            if checks.is_empty() {
+                return Err(miette!(
+                    "No solutions registered for {}",
+                    run::describe(year, day, part)
+                ));
+            }
+            print!("{}", verify::table(&checks));
+
+            let journal_path = journal::path(&inputs);
+            let mut journal = Journal::load(&journal_path)?;
+            if verify::note_solved(
+                &checks,
+                &mut journal,
+                journal::now(),
+            ) {
+                journal.save(&journal_path)?;
+            }
+
+            if record {
+                let mut recorded = 0;
+                for check in &checks {
+                    if let (
+                        Status::Unrecorded,
+                        Some(answer),
+                    ) = (&check.status, &check.answer)
+                    {
+                        let solution = check.solution;
+                        answers.set(
+                            solution.year(),
+                            solution.day(),
+                            solution.part(),
+                            &answer.to_string(),
+                        );
+                        recorded += 1;
+                    }
+                }
+                if recorded > 0 {
+                    answers.save(&path)?;
+                    println!(
+                        "Recorded {recorded} answers in {}",
+                        path.display()
+                    );
+                }
+            }
+
+            let failed = checks
+                .iter()
+                .filter(|c| c.failed())
+                .count();
+            if failed > 0 {
+                Err(miette!(
+                    "{failed} of {} solutions don't match their recorded answers",
+                    checks.len()
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        Command::Wait {
+            year,
+            day,
+            inputs,
+            site,
+        } => {
+            let clock = SystemClock;
+            let (year, day) = match (year, day) {
+                (Some(year), Some(day)) => (year, day),
+                (None, Some(day)) => {
+                    (wait::next_unlock(clock.now()).0, day)
+                }
+                _ => wait::next_unlock(clock.now()),
+            };
+            let inputs =
+                inputs.unwrap_or_else(input::default_dir);
+            // Connect first, so a missing session
+            // shows up before the wait, not after.
+            let (client, origin) = site.connect()?;
+            println!(
+                "Waiting for {year} day {day:02} with session {} from {origin}",
+                client.session()
+            );
+
+            let unlock = wait::unlock_time(year, day);
+            if unlock > clock.now() {
+                wait::countdown(
+                    &clock,
+                    &format!("{year} day {day:02}"),
+                    unlock,
+                    &mut io::stdout(),
+                )
+                .into_diagnostic()?;
+            }
+            wait::fetch(
+                &client,
+                &clock,
+                &inputs,
+                year,
+                day,
+                &input::day_dir(year, day),
+                || wait::scaffold(year, day),
+            )
+        }
+        Command::Leaderboard {
+            year,
+            day,
+            id,
+            site,
+        } => {
+            let id = match id {
+                Some(id) => Some(id),
+                None => config::default_path()
+                    .map(|path| {
+                        Config::load(&path)
+                            .map_err(Report::from)
+                    })
+                    .transpose()?
+                    .and_then(|config| config.leaderboard),
+            };
+            let id = id.ok_or_else(|| {
+                miette!(
+                    help = "Pass `--id`, set `AOC_LEADERBOARD` or \
+                            `leaderboard` in the config file.",
+                    "No leaderboard given"
+                )
+            })?;
+            let cache = fetch_input::leaderboard::default_cache_dir()
+                .ok_or_else(|| miette!("No cache directory"))?;
+
+            let (client, _) = site.connect()?;
+            let now = SystemTime::now();
+            let snapshot = fetch_input::leaderboard::load(
+                &client, year, id, &cache, now,
+            )?;
+            let age = now
+                .duration_since(snapshot.fetched_at)
+                .unwrap_or_default();
+            println!(
+                "Leaderboard {id} of {year}, downloaded {} ago\n",
+                wait::format_duration(age)
+            );
+
+            let leaderboard = &snapshot.leaderboard;
+            print!(
+                "{}",
+                leaderboard::standings(
+                    leaderboard,
+                    snapshot.previous.as_ref()
+                )
+            );
+            let days = match day {
+                Some(day) => vec![day],
+                None => {
+                    leaderboard.days().into_iter().collect()
+                }
+            };
+            for day in days {
+                print!(
+                    "\n{}",
+                    leaderboard::day(
+                        leaderboard,
+                        year,
+                        day
+                    )
+                );
+            }
+            Ok(())
+        }
+        Command::Stats { year, inputs, json } => {
+            let inputs =
+                inputs.unwrap_or_else(input::default_dir);
+            let journal =
+                Journal::load(&journal::path(&inputs))?;
+            let rows = stats::rows(&journal, year);
+            if json {
+                println!("{}", stats::json(&rows));
+            } else if rows.is_empty() {
+                println!(
+                    "Nothing in the journal yet; it fills up \
+                     as inputs are fetched and answers \
+                     submitted or verified."
+                );
+            } else {
+                print!("{}", stats::table(&rows));
+            }
+            Ok(())
+        }
+        Command::List => {
+            for solution in registry.iter() {
+                println!("{solution}");
+            }
+            Ok(())
+        }
+        Command::Register { year, day } => {
+            wait::register(year, day)
+        }
+    }
+}
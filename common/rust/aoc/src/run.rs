@@ -0,0 +1,213 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use aoc_core::{
+    Answer, Part, Registry, Solution, input::Source,
+};
+use miette::miette;
+
+/// Runs every solution matching the filters,
+/// printing each answer with its wall-clock time.
+/// Failures are reported as they happen and don't
+/// stop the rest.
+pub fn run(
+    registry: &Registry,
+    source: &Source,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+) -> miette::Result<()> {
+    let solutions: Vec<_> =
+        registry.select(year, day, part).collect();
+    if solutions.is_empty() {
+        return Err(miette!(
+            "No solutions registered for {}",
+            describe(year, day, part)
+        ));
+    }
+
+    // Stdin can only be read once, so both parts
+    // get the same copy.
+    let stdin = match source {
+        Source::Stdin => Some(
+            std::io::read_to_string(std::io::stdin())
+                .map_err(aoc_core::AocError::from)?,
+        ),
+        _ => None,
+    };
+
+    let mut failed = 0;
+    for &solution in &solutions {
+        let input = match &stdin {
+            Some(input) => Ok(input.clone()),
+            None => source.load(solution),
+        };
+        match input {
+            Ok(input) => {
+                let (result, elapsed) =
+                    solve(solution, &input);
+                match result {
+                    Ok(answer @ Answer::Art(_)) => {
+                        println!(
+                            "{solution}: ({elapsed:.2?})\n{answer}"
+                        );
+                    }
+                    Ok(answer) => {
+                        println!(
+                            "{solution}: {answer} ({elapsed:.2?})"
+                        );
+                    }
+                    Err(report) => {
+                        failed += 1;
+                        eprintln!(
+                            "{solution}: failed after {elapsed:.2?}"
+                        );
+                        eprintln!("{report:?}");
+                    }
+                }
+            }
+            Err(report) => {
+                failed += 1;
+                eprintln!("{solution}: no input");
+                eprintln!(
+                    "{:?}",
+                    miette::Report::new(report)
+                );
+            }
+        }
+    }
+
+    if failed > 0 {
+        Err(miette!(
+            "{failed} of {} solutions failed",
+            solutions.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs a single solution, turning panics (e.g. a
+/// `todo!()` in a fresh day) into errors.
+pub fn solve(
+    solution: &dyn Solution,
+    input: &str,
+) -> (miette::Result<Answer>, Duration) {
+    let started = Instant::now();
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            solution.process(input)
+        }));
+    let elapsed = started.elapsed();
+
+    let result = result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| {
+                payload.downcast_ref::<String>().cloned()
+            })
+            .unwrap_or_default();
+        Err(miette!(
+            "{solution} panicked: {message}"
+        ))
+    });
+    (result, elapsed)
+}
+
+pub fn describe(
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+) -> String {
+    match (year, day, part) {
+        (None, ..) => "any year".to_string(),
+        (Some(year), None, _) => year.to_string(),
+        (Some(year), Some(day), None) => {
+            format!("{year} day {day:02}")
+        }
+        (Some(year), Some(day), Some(part)) => {
+            format!("{year} day {day:02} part {part}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::solution::Process;
+
+    use super::*;
+
+    fn answer(input: &str) -> miette::Result<Answer> {
+        Ok(input.lines().count().into())
+    }
+
+    fn unfinished(_input: &str) -> miette::Result<Answer> {
+        todo!("day 99 - part 2");
+    }
+
+    const ANSWER: Process = Process {
+        year: 1999,
+        day: 1,
+        part: Part::One,
+        process: answer,
+        dir: env!("CARGO_MANIFEST_DIR"),
+    };
+
+    const UNFINISHED: Process = Process {
+        year: 1999,
+        day: 1,
+        part: Part::Two,
+        process: unfinished,
+        dir: env!("CARGO_MANIFEST_DIR"),
+    };
+
+    #[test]
+    fn test_solve() -> miette::Result<()> {
+        let (result, _) = solve(&ANSWER, "a\nb\nc");
+        assert_eq!(Answer::Int(3), result?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_catches_panics() {
+        let (result, _) = solve(&UNFINISHED, "");
+        let report = result.unwrap_err();
+        assert_eq!(
+            "1999 day 01 part 2 panicked: not yet implemented: day 99 - part 2",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn test_run_without_input() {
+        let inputs =
+            std::env::temp_dir().join("aoc-no-inputs");
+        let registry: Registry = [&ANSWER as &dyn Solution]
+            .into_iter()
+            .collect();
+        let report = run(
+            &registry,
+            &Source::Dir(inputs),
+            Some(1999),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "1 of 1 solutions failed",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!("any year", describe(None, None, None));
+        assert_eq!(
+            "2024 day 05 part 2",
+            describe(Some(2024), Some(5), Some(Part::Two))
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use fetch_input::journal::{Entry, Journal};
+use serde::Serialize;
+
+use crate::{table, wait};
+
+/// One day of the journal, with how long each
+/// part took from fetching the input.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    /// Unix seconds, as in the journal.
+    pub fetched: Option<u64>,
+    pub part1: Option<u64>,
+    pub part2: Option<u64>,
+    /// Seconds from fetching to solving.
+    pub part1_time: Option<u64>,
+    pub part2_time: Option<u64>,
+}
+
+impl Row {
+    fn new(year: u16, day: u8, entry: &Entry) -> Row {
+        let since_fetched = |solved: Option<u64>| {
+            solved?.checked_sub(entry.fetched?)
+        };
+        Row {
+            year,
+            day,
+            fetched: entry.fetched,
+            part1: entry.part1,
+            part2: entry.part2,
+            part1_time: since_fetched(entry.part1),
+            part2_time: since_fetched(entry.part2),
+        }
+    }
+}
+
+/// Every day in the journal, or only those of
+/// `year`.
+pub fn rows(
+    journal: &Journal,
+    year: Option<u16>,
+) -> Vec<Row> {
+    journal
+        .entries()
+        .filter(|&(y, ..)| {
+            year.is_none_or(|year| year == y)
+        })
+        .map(|(year, day, entry)| {
+            Row::new(year, day, entry)
+        })
+        .collect()
+}
+
+fn duration(seconds: Option<u64>) -> String {
+    seconds
+        .map(|seconds| {
+            wait::format_duration(Duration::from_secs(
+                seconds,
+            ))
+        })
+        .unwrap_or_default()
+}
+
+/// Lays the rows out as a table, one day per row.
+pub fn table(rows: &[Row]) -> String {
+    let rows: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let between = row
+                .part2
+                .zip(row.part1)
+                .and_then(|(part2, part1)| {
+                    part2.checked_sub(part1)
+                });
+            [
+                format!("{} day {:02}", row.year, row.day),
+                duration(row.part1_time),
+                duration(row.part2_time),
+                duration(between),
+            ]
+        })
+        .collect();
+    table::render(
+        ["day", "part 1", "part 2", "part 1 to 2"],
+        &rows,
+    )
+}
+
+pub fn json(rows: &[Row]) -> String {
+    serde_json::to_string_pretty(rows)
+        .expect("rows serialize to JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Part;
+
+    use super::*;
+
+    fn journal() -> Journal {
+        let mut journal = Journal::default();
+        journal.fetched(2023, 1, 1_000);
+        journal.fetched(2024, 5, 1_000);
+        journal.solved(2024, 5, Part::One, 1_000 + 754);
+        journal.solved(2024, 5, Part::Two, 1_000 + 3_723);
+        // Verified before the journal knew about
+        // the input.
+        journal.solved(2024, 6, Part::One, 5_000);
+        journal
+    }
+
+    #[test]
+    fn test_rows() {
+        assert_eq!(
+            vec![Row {
+                year: 2024,
+                day: 5,
+                fetched: Some(1_000),
+                part1: Some(1_754),
+                part2: Some(4_723),
+                part1_time: Some(754),
+                part2_time: Some(3_723),
+            }],
+            rows(&journal(), Some(2024))
+                .into_iter()
+                .filter(|row| row.day == 5)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, rows(&journal(), None).len());
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            "\
+day          part 1    part 2    part 1 to 2
+-----------  --------  --------  -----------
+2023 day 01
+2024 day 05  00:12:34  01:02:03  00:49:29
+2024 day 06
+",
+            table(&rows(&journal(), None))
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json = json(&rows(&journal(), Some(2023)));
+        assert_eq!(
+            r#"[
+  {
+    "year": 2023,
+    "day": 1,
+    "fetched": 1000,
+    "part1": null,
+    "part2": null,
+    "part1_time": null,
+    "part2_time": null
+  }
+]"#,
+            json
+        );
+    }
+}
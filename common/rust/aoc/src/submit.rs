@@ -0,0 +1,215 @@
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::{Answer, Part};
+use fetch_input::{
+    Client,
+    history::{self, History},
+    journal,
+    submit::Verdict,
+};
+use miette::miette;
+
+/// The text to submit for an answer. Letter art
+/// in a font that can't be read has to be read
+/// off first.
+pub fn text(answer: &Answer) -> miette::Result<String> {
+    match answer {
+        Answer::Art(art) => Err(miette!(
+            help = "Read the letters off and pass them \
+                    with `--answer`.",
+            "The answer is letter art in an unknown font:\n{art}"
+        )),
+        answer => Ok(answer.to_string()),
+    }
+}
+
+/// Submits `answer` unless the day's history
+/// already shows it can't be right, and records
+/// whatever the site says about it. `force` skips
+/// the check.
+pub fn submit(
+    client: &Client,
+    inputs: &Path,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+    force: bool,
+) -> miette::Result<Verdict> {
+    let path = history::path(inputs, year, day);
+    let mut history = History::load(&path)?;
+    if !force {
+        history.check(part, answer)?;
+    }
+
+    let verdict = client.submit(year, day, part, answer)?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    history.record(part, answer, &verdict, now);
+    history.save(&path)?;
+    if verdict == Verdict::Correct {
+        journal::record_solved(inputs, year, day, part)?;
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fetch_input::{
+        FetchError,
+        history::Outcome,
+        http::{Http, Settings},
+        session::Session,
+    };
+    use mockito::{Matcher, Server, ServerGuard};
+
+    use super::*;
+
+    fn client(server: &ServerGuard) -> Client {
+        let http = Http::new(Settings {
+            min_interval: Duration::ZERO,
+            state: None,
+            ..Settings::default()
+        });
+        Client::with_http(
+            server.url(),
+            Session::new("cafebabe"),
+            http,
+        )
+    }
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main>\
+             <article><p>{message}</p></article>\
+             </main></body></html>"
+        )
+    }
+
+    #[test]
+    fn test_submit_records_attempts() -> miette::Result<()>
+    {
+        let mut server = Server::new();
+        let too_high = server
+            .mock("POST", "/2024/day/5/answer")
+            .match_body(Matcher::UrlEncoded(
+                "answer".into(),
+                "500".into(),
+            ))
+            .with_body(page(
+                "That's not the right answer; your answer is too high.",
+            ))
+            .create();
+        let correct = server
+            .mock("POST", "/2024/day/5/answer")
+            .match_body(Matcher::UrlEncoded(
+                "answer".into(),
+                "143".into(),
+            ))
+            .with_body(page("That's the right answer!"))
+            .create();
+
+        let inputs = tempfile::tempdir().unwrap();
+        let client = client(&server);
+        let submit = |answer| {
+            submit(
+                &client,
+                inputs.path(),
+                2024,
+                5,
+                Part::One,
+                answer,
+                false,
+            )
+        };
+
+        assert!(matches!(
+            submit("500")?,
+            Verdict::Wrong { .. }
+        ));
+        assert!(matches!(
+            submit("600").unwrap_err().downcast_ref(),
+            Some(FetchError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            submit("500").unwrap_err().downcast_ref(),
+            Some(FetchError::KnownWrong {
+                outcome: Outcome::TooHigh,
+                ..
+            })
+        ));
+        let journal_path = journal::path(inputs.path());
+        assert!(!journal_path.exists());
+        assert_eq!(Verdict::Correct, submit("143")?);
+        let solved = journal::Journal::load(&journal_path)?
+            .get(2024, 5)
+            .and_then(|entry| entry.part1);
+        assert!(solved.is_some());
+        assert!(matches!(
+            submit("144").unwrap_err().downcast_ref(),
+            Some(FetchError::AlreadySolved { .. })
+        ));
+
+        too_high.expect(1).assert();
+        correct.expect(1).assert();
+
+        let history = History::load(&history::path(
+            inputs.path(),
+            2024,
+            5,
+        ))?;
+        assert_eq!(
+            vec![
+                ("500", Outcome::TooHigh),
+                ("143", Outcome::Correct)
+            ],
+            history
+                .attempts
+                .iter()
+                .map(|a| (a.answer.as_str(), a.outcome))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit_force() -> miette::Result<()> {
+        let mut server = Server::new();
+        let mock = server
+            .mock("POST", "/2024/day/5/answer")
+            .with_body(page(
+                "That's not the right answer; your answer is too high.",
+            ))
+            .expect(2)
+            .create();
+
+        let inputs = tempfile::tempdir().unwrap();
+        let client = client(&server);
+        for force in [false, true] {
+            let verdict = submit(
+                &client,
+                inputs.path(),
+                2024,
+                5,
+                Part::Two,
+                "500",
+                force,
+            )?;
+            assert!(matches!(
+                verdict,
+                Verdict::Wrong { .. }
+            ));
+        }
+
+        mock.assert();
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+/// Lays `rows` out in left-aligned columns under
+/// `header`, with a line of dashes between them.
+pub fn render<const N: usize>(
+    header: [&str; N],
+    rows: &[[String; N]],
+) -> String {
+    let header = header.map(String::from);
+    let mut widths = [0; N];
+    for row in std::iter::once(&header).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &[String; N]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+    let mut table = line(&header);
+    table.push_str(&line(
+        &widths.map(|width| "-".repeat(width)),
+    ));
+    for row in rows {
+        table.push_str(&line(row));
+    }
+    table
+}
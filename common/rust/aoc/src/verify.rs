@@ -0,0 +1,331 @@
+use std::{fmt, time::Duration};
+
+use aoc_core::{
+    Answer, Part, Registry, Solution, input::Source,
+};
+use fetch_input::journal::Journal;
+
+use crate::{answers::Answers, run::solve, table};
+
+/// How a solution compares to its recorded
+/// answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// It ran, but there is nothing to compare
+    /// its answer to.
+    Unrecorded,
+    NoInput,
+    /// It failed; only counts against it when
+    /// there is an answer it should have given.
+    Error {
+        message: String,
+        recorded: bool,
+    },
+}
+
+impl fmt::Display for Status {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(match self {
+            Status::Pass => "pass",
+            Status::Fail { .. }
+            | Status::Error { recorded: true, .. } => {
+                "FAIL"
+            }
+            Status::Error {
+                recorded: false, ..
+            } => "error",
+            Status::Unrecorded | Status::NoInput => {
+                "missing"
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub solution: &'static dyn Solution,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    pub elapsed: Option<Duration>,
+}
+
+impl Check {
+    pub fn failed(&self) -> bool {
+        matches!(
+            self.status,
+            Status::Fail { .. }
+                | Status::Error { recorded: true, .. }
+        )
+    }
+
+    fn note(&self) -> String {
+        match &self.status {
+            Status::Pass => String::new(),
+            Status::Fail { expected } => {
+                format!("expected {expected}")
+            }
+            Status::Unrecorded => {
+                "no answer recorded".to_string()
+            }
+            Status::NoInput => "no input".to_string(),
+            Status::Error { message, .. } => {
+                message.clone()
+            }
+        }
+    }
+}
+
+/// Runs every solution matching the filters and
+/// compares its answer with the recorded one.
+pub fn verify(
+    registry: &Registry,
+    source: &Source,
+    answers: &Answers,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+) -> Vec<Check> {
+    registry
+        .select(year, day, part)
+        .map(|solution| {
+            let Ok(input) = source.load(solution) else {
+                return Check {
+                    solution,
+                    status: Status::NoInput,
+                    answer: None,
+                    elapsed: None,
+                };
+            };
+            let (result, elapsed) = solve(solution, &input);
+            let expected = answers.get(
+                solution.year(),
+                solution.day(),
+                solution.part(),
+            );
+            let (status, answer) = match (result, expected)
+            {
+                (Err(report), expected) => (
+                    Status::Error {
+                        message: report.to_string(),
+                        recorded: expected.is_some(),
+                    },
+                    None,
+                ),
+                (Ok(answer), None) => {
+                    (Status::Unrecorded, Some(answer))
+                }
+                (Ok(answer), Some(expected))
+                    if answer
+                        == Answer::parse(expected) =>
+                {
+                    (Status::Pass, Some(answer))
+                }
+                (Ok(answer), Some(expected)) => (
+                    Status::Fail {
+                        expected: expected.to_string(),
+                    },
+                    Some(answer),
+                ),
+            };
+            Check {
+                solution,
+                status,
+                answer,
+                elapsed: Some(elapsed),
+            }
+        })
+        .collect()
+}
+
+/// Notes the passing solutions as solved in the
+/// journal, unless they already were. Returns
+/// whether anything changed.
+pub fn note_solved(
+    checks: &[Check],
+    journal: &mut Journal,
+    at: u64,
+) -> bool {
+    let mut changed = false;
+    for check in checks {
+        if check.status == Status::Pass {
+            let solution = check.solution;
+            changed |= journal.solved(
+                solution.year(),
+                solution.day(),
+                solution.part(),
+                at,
+            );
+        }
+    }
+    changed
+}
+
+/// Lays the checks out as a table, one solution
+/// per row.
+pub fn table(checks: &[Check]) -> String {
+    let rows: Vec<[String; 5]> = checks
+        .iter()
+        .map(|check| {
+            [
+                check.solution.to_string(),
+                check.status.to_string(),
+                match &check.answer {
+                    Some(Answer::Art(_)) => {
+                        "(letter art)".to_string()
+                    }
+                    Some(answer) => answer.to_string(),
+                    None => String::new(),
+                },
+                check
+                    .elapsed
+                    .map(|elapsed| format!("{elapsed:.2?}"))
+                    .unwrap_or_default(),
+                check.note(),
+            ]
+        })
+        .collect();
+    table::render(
+        ["solution", "result", "answer", "time", ""],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::solution::Process;
+
+    use super::*;
+
+    fn count(input: &str) -> miette::Result<Answer> {
+        Ok(input.lines().count().into())
+    }
+
+    fn broken(_input: &str) -> miette::Result<Answer> {
+        Err(miette::miette!("broken"))
+    }
+
+    const fn process(
+        day: u8,
+        part: Part,
+        process: fn(&str) -> miette::Result<Answer>,
+    ) -> Process {
+        Process {
+            year: 1999,
+            day,
+            part,
+            process,
+            dir: env!("CARGO_MANIFEST_DIR"),
+        }
+    }
+
+    static PASS: Process = process(1, Part::One, count);
+    static FAIL: Process = process(1, Part::Two, count);
+    static UNRECORDED: Process =
+        process(2, Part::One, count);
+    static ERROR: Process = process(2, Part::Two, broken);
+    static NO_INPUT: Process = process(3, Part::One, count);
+
+    fn checks() -> Vec<Check> {
+        let inputs = tempfile::tempdir().unwrap();
+        for day in [1, 2] {
+            let path = aoc_core::input::path(
+                inputs.path(),
+                1999,
+                day,
+            );
+            std::fs::create_dir_all(path.parent().unwrap())
+                .unwrap();
+            std::fs::write(path, "a\nb\nc\n").unwrap();
+        }
+        let mut answers = Answers::default();
+        answers.set(1999, 1, Part::One, "3");
+        answers.set(1999, 1, Part::Two, "4");
+        answers.set(1999, 2, Part::Two, "1");
+        answers.set(1999, 3, Part::One, "1");
+
+        let registry: Registry =
+            [&PASS, &FAIL, &UNRECORDED, &ERROR, &NO_INPUT]
+                .into_iter()
+                .map(|p| p as &dyn Solution)
+                .collect();
+        verify(
+            &registry,
+            &Source::Dir(inputs.path().to_path_buf()),
+            &answers,
+            Some(1999),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_verify() {
+        let statuses: Vec<_> = checks()
+            .into_iter()
+            .map(|check| (check.status, check.answer))
+            .collect();
+        assert_eq!(
+            vec![
+                (Status::Pass, Some(Answer::Int(3))),
+                (
+                    Status::Fail {
+                        expected: "4".to_string()
+                    },
+                    Some(Answer::Int(3))
+                ),
+                (Status::Unrecorded, Some(Answer::Int(3))),
+                (
+                    Status::Error {
+                        message: "broken".to_string(),
+                        recorded: true,
+                    },
+                    None
+                ),
+                (Status::NoInput, None),
+            ],
+            statuses
+        );
+    }
+
+    #[test]
+    fn test_note_solved() {
+        let checks = checks();
+        let mut journal = Journal::default();
+        assert!(note_solved(&checks, &mut journal, 100));
+        assert!(!note_solved(&checks, &mut journal, 200));
+
+        let entry = journal.get(1999, 1).unwrap();
+        assert_eq!(Some(100), entry.part1);
+        assert_eq!(None, entry.part2);
+        assert_eq!(None, journal.get(1999, 2));
+    }
+
+    #[test]
+    fn test_table() {
+        let mut checks = checks();
+        for check in &mut checks {
+            check.elapsed = check
+                .elapsed
+                .map(|_| Duration::from_millis(1));
+        }
+        assert_eq!(
+            "\
+solution            result   answer  time
+------------------  -------  ------  ------  ------------------
+1999 day 01 part 1  pass     3       1.00ms
+1999 day 01 part 2  FAIL     3       1.00ms  expected 4
+1999 day 02 part 1  missing  3       1.00ms  no answer recorded
+1999 day 02 part 2  FAIL             1.00ms  broken
+1999 day 03 part 1  missing                  no input
+",
+            table(&checks)
+        );
+    }
+}
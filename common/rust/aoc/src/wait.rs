@@ -0,0 +1,576 @@
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::input;
+use fetch_input::{
+    Client, FetchError, cli::days_in, journal, puzzle,
+};
+use miette::{IntoDiagnostic, miette};
+
+/// Puzzles unlock at midnight US Eastern, which
+/// in December is always EST, five hours behind
+/// UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// How often to ask again when the site says the
+/// puzzle isn't out yet, e.g. because the local
+/// clock is a little ahead.
+const NOT_UNLOCKED_RETRIES: u32 = 10;
+const NOT_UNLOCKED_DELAY: Duration = Duration::from_secs(2);
+
+/// Where the time comes from, so waiting can be
+/// tested without waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Days between 1970-01-01 and a date in the
+/// proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Howard Hinnant's `days_from_civil`, with
+    // years starting in March so leap days come
+    // last.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4
+        - year_of_era / 100
+        + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When a day's puzzle unlocks.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into());
+    let seconds =
+        days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3600;
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// The first puzzle to unlock after `now`.
+pub fn next_unlock(now: SystemTime) -> (u16, u8) {
+    let seconds = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // Close enough to find the current year; the
+    // ones around it are checked too.
+    let year = (1970 + seconds / 31_556_952) as u16;
+    (year - 1..=year + 1)
+        .flat_map(|year| {
+            days_in(year).map(move |day| (year, day))
+        })
+        .find(|&(year, day)| unlock_time(year, day) > now)
+        .expect("a puzzle unlocks within a year")
+}
+
+/// `HH:MM:SS`, led by the number of days if there
+/// are any.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, seconds) =
+        (seconds / 86_400, seconds % 86_400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    match days {
+        0 => clock,
+        1 => format!("1 day {clock}"),
+        days => format!("{days} days {clock}"),
+    }
+}
+
+/// Counts down to `until` on `out`, a line that
+/// is rewritten every second. Part of a second
+/// left counts as a whole one, so the last one
+/// shown is `00:00:01`.
+pub fn countdown(
+    clock: &dyn Clock,
+    label: &str,
+    until: SystemTime,
+    out: &mut dyn Write,
+) -> std::io::Result<()> {
+    while let Ok(remaining) =
+        until.duration_since(clock.now())
+        && !remaining.is_zero()
+    {
+        let shown = Duration::from_secs(
+            remaining.as_secs()
+                + u64::from(remaining.subsec_nanos() > 0),
+        );
+        write!(
+            out,
+            "\r{label} unlocks in {}",
+            format_duration(shown)
+        )?;
+        out.flush()?;
+        // Wake up on the second, so the display
+        // ticks evenly and the end isn't missed.
+        let to_next_second = Duration::from_nanos(
+            remaining.subsec_nanos().into(),
+        );
+        clock.sleep(if to_next_second.is_zero() {
+            Duration::from_secs(1).min(remaining)
+        } else {
+            to_next_second
+        });
+    }
+    writeln!(out, "\r{label} is unlocked!{:20}", "")
+}
+
+/// Creates a day's crate from its year's
+/// `day-template`, like `just create` does, and
+/// registers it with the `aoc` runner.
+pub fn scaffold(year: u16, day: u8) -> miette::Result<()> {
+    let day_dir = input::day_dir(year, day);
+    let workspace = day_dir
+        .parent()
+        .expect("a day's crate lives in a workspace");
+    if !workspace.join("day-template").is_dir() {
+        return Err(miette!(
+            "There is no day template in {}",
+            workspace.display()
+        ));
+    }
+
+    let status = Command::new("cargo")
+        .args(["generate", "--path", "./day-template"])
+        .arg("--name")
+        .arg(format!("day-{day:02}"))
+        .current_dir(workspace)
+        .status()
+        .into_diagnostic()?;
+    if !status.success() {
+        return Err(miette!(
+            help = "Is cargo-generate installed?",
+            "Couldn't create {}",
+            day_dir.display()
+        ));
+    }
+    register(year, day)
+}
+
+/// Adds `entry` to `text` among the lines
+/// `is_entry` holds for, keeping them sorted.
+/// `None` if it's already there or there are no
+/// such lines to go among.
+fn insert_sorted(
+    text: &str,
+    is_entry: impl Fn(&str) -> bool,
+    entry: &str,
+) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&entry) {
+        return None;
+    }
+    let entries: Vec<usize> = (0..lines.len())
+        .filter(|&i| is_entry(lines[i]))
+        .collect();
+    let at = entries
+        .iter()
+        .find(|&&i| lines[i] > entry)
+        .copied()
+        .or_else(|| entries.last().map(|last| last + 1))?;
+    lines.insert(at, entry);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds a day's crate to the dependencies and
+/// `src/days.rs` of this crate, so that the next
+/// build links its solutions in.
+pub fn register(year: u16, day: u8) -> miette::Result<()> {
+    register_in(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        year,
+        day,
+    )
+}
+
+/// [`register`] for the `aoc` crate in `aoc_dir`.
+fn register_in(
+    aoc_dir: &Path,
+    year: u16,
+    day: u8,
+) -> miette::Result<()> {
+    let name = format!("aoc-{year}-day-{day:02}");
+    let files = [
+        (
+            aoc_dir.join("Cargo.toml"),
+            format!(
+                "{name} = {{ path = \"../../../{year}/rust/day-{day:02}\" }}"
+            ),
+            // Not `aoc-core` and the like.
+            "aoc-20",
+        ),
+        (
+            aoc_dir.join("src").join("days.rs"),
+            format!("use {} as _;", name.replace('-', "_")),
+            "use aoc_20",
+        ),
+    ];
+    for (path, entry, prefix) in files {
+        let text =
+            fs::read_to_string(&path).into_diagnostic()?;
+        let Some(text) = insert_sorted(
+            &text,
+            |line| line.starts_with(prefix),
+            &entry,
+        ) else {
+            if !text.lines().any(|line| line == entry) {
+                return Err(miette!(
+                    help = "Add `{entry}` by hand.",
+                    "Couldn't find where to register {name} in {}",
+                    path.display()
+                ));
+            }
+            continue;
+        };
+        fs::write(&path, text).into_diagnostic()?;
+        println!(
+            "Registered {name} in {}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Downloads the input into `inputs` as soon as
+/// the site has it, scaffolds the day's crate if
+/// `day_dir` doesn't exist yet and saves the
+/// description and examples into it.
+pub fn fetch(
+    client: &Client,
+    clock: &dyn Clock,
+    inputs: &Path,
+    year: u16,
+    day: u8,
+    day_dir: &Path,
+    scaffold: impl FnOnce() -> miette::Result<()>,
+) -> miette::Result<()> {
+    let path = input::path(inputs, year, day);
+    let mut retries = 0;
+    loop {
+        match client.download_input(year, day, &path) {
+            Ok(()) => break,
+            Err(FetchError::NotUnlocked { .. })
+                if retries < NOT_UNLOCKED_RETRIES =>
+            {
+                retries += 1;
+                clock.sleep(NOT_UNLOCKED_DELAY);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    println!("Wrote {}", path.display());
+    journal::record_fetched(inputs, year, day)?;
+
+    if !day_dir.exists() {
+        scaffold()?;
+    }
+
+    for path in
+        puzzle::download(client, year, day, day_dir)?
+    {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use fetch_input::{
+        http::{Http, Settings},
+        session::Session,
+    };
+    use mockito::Server;
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Starts at `now` and only moves when slept.
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        slept: Cell<u32>,
+    }
+
+    impl FakeClock {
+        fn at(now: SystemTime) -> Self {
+            FakeClock {
+                now: Cell::new(now),
+                slept: Cell::new(0),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.slept.set(self.slept.get() + 1);
+        }
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[rstest]
+    // 2024-12-01T05:00:00Z
+    #[case(2024, 1, 1_733_029_200)]
+    // 2024-12-25T05:00:00Z
+    #[case(2024, 25, 1_735_102_800)]
+    // 2023-12-05T05:00:00Z
+    #[case(2023, 5, 1_701_752_400)]
+    fn test_unlock_time(
+        #[case] year: u16,
+        #[case] day: u8,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(at(expected), unlock_time(year, day));
+    }
+
+    #[rstest]
+    // Mid-year: the next December.
+    #[case(1_720_000_000, (2024, 1))]
+    // A second before day 1 unlocks.
+    #[case(1_733_029_199, (2024, 1))]
+    // The moment it unlocks: on to day 2.
+    #[case(1_733_029_200, (2024, 2))]
+    // After day 25: next year.
+    #[case(1_735_102_800, (2025, 1))]
+    // After 2025's last day, 12: the next year.
+    #[case(1_765_515_600, (2026, 1))]
+    fn test_next_unlock(
+        #[case] now: u64,
+        #[case] expected: (u16, u8),
+    ) {
+        assert_eq!(expected, next_unlock(at(now)));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(
+            "01:02:03",
+            format_duration(Duration::from_secs(3723))
+        );
+        assert_eq!(
+            "2 days 00:00:05",
+            format_duration(Duration::from_secs(172_805))
+        );
+    }
+
+    #[rstest]
+    #[case::on_the_second(3000)]
+    #[case::between_seconds(2500)]
+    fn test_countdown(
+        #[case] millis: u64,
+    ) -> std::io::Result<()> {
+        let unlock = unlock_time(2024, 1);
+        let clock = FakeClock::at(
+            unlock - Duration::from_millis(millis),
+        );
+        let mut out = vec![];
+        countdown(&clock, "2024 day 01", unlock, &mut out)?;
+
+        assert_eq!(unlock, clock.now());
+        assert_eq!(
+            "\r2024 day 01 unlocks in 00:00:03\
+             \r2024 day 01 unlocks in 00:00:02\
+             \r2024 day 01 unlocks in 00:00:01\
+             \r2024 day 01 is unlocked!                    \n",
+            String::from_utf8(out).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_countdown_after_unlock() -> std::io::Result<()>
+    {
+        let unlock = unlock_time(2024, 1);
+        let clock =
+            FakeClock::at(unlock + Duration::from_secs(1));
+        let mut out = vec![];
+        countdown(&clock, "2024 day 01", unlock, &mut out)?;
+        assert_eq!(0, clock.slept.get());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register() -> miette::Result<()> {
+        let aoc = tempfile::tempdir().unwrap();
+        fs::create_dir(aoc.path().join("src")).unwrap();
+        fs::write(
+            aoc.path().join("Cargo.toml"),
+            "[dependencies]\n\
+             aoc-core.workspace = true\n\
+             clap.workspace = true\n\
+             \n\
+             aoc-2023-day-01 = { path = \"../../../2023/rust/day-01\" }\n\
+             aoc-2024-day-01 = { path = \"../../../2024/rust/day-01\" }\n\
+             \n\
+             [dev-dependencies]\n",
+        )
+        .unwrap();
+        fs::write(
+            aoc.path().join("src/days.rs"),
+            "//! Links every day crate.\n\
+             \n\
+             use aoc_2023_day_01 as _;\n\
+             use aoc_2024_day_01 as _;\n",
+        )
+        .unwrap();
+
+        register_in(aoc.path(), 2024, 2)?;
+        register_in(aoc.path(), 2023, 2)?;
+        // Registering again changes nothing.
+        register_in(aoc.path(), 2024, 2)?;
+
+        assert_eq!(
+            "[dependencies]\n\
+             aoc-core.workspace = true\n\
+             clap.workspace = true\n\
+             \n\
+             aoc-2023-day-01 = { path = \"../../../2023/rust/day-01\" }\n\
+             aoc-2023-day-02 = { path = \"../../../2023/rust/day-02\" }\n\
+             aoc-2024-day-01 = { path = \"../../../2024/rust/day-01\" }\n\
+             aoc-2024-day-02 = { path = \"../../../2024/rust/day-02\" }\n\
+             \n\
+             [dev-dependencies]\n",
+            fs::read_to_string(
+                aoc.path().join("Cargo.toml")
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "//! Links every day crate.\n\
+             \n\
+             use aoc_2023_day_01 as _;\n\
+             use aoc_2023_day_02 as _;\n\
+             use aoc_2024_day_01 as _;\n\
+             use aoc_2024_day_02 as _;\n",
+            fs::read_to_string(
+                aoc.path().join("src/days.rs")
+            )
+            .unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch() -> miette::Result<()> {
+        let mut server = Server::new();
+        let locked = server
+            .mock("GET", "/2024/day/1/input")
+            .with_status(404)
+            .with_body(
+                "Please don't repeatedly request this endpoint before it unlocks! \
+                 The calendar countdown is synchronized with the server time; \
+                 the link will be enabled on the calendar the instant this \
+                 puzzle becomes available.\n",
+            )
+            .create();
+        let input = server
+            .mock("GET", "/2024/day/1/input")
+            .with_body("3   4\n")
+            .create();
+        let page = server
+            .mock("GET", "/2024/day/1")
+            .with_body(
+                "<main><article class=\"day-desc\">\
+                 <h2>--- Day 1: Historian Hysteria ---</h2>\
+                 <p>For example:</p><pre><code>3   4\n</code></pre>\
+                 <p>A total distance of <code><em>1</em></code>!</p>\
+                 </article></main>",
+            )
+            .create();
+
+        let http = Http::new(Settings {
+            min_interval: Duration::ZERO,
+            state: None,
+            ..Settings::default()
+        });
+        let client = Client::with_http(
+            server.url(),
+            Session::new("cafebabe"),
+            http,
+        );
+        let clock = FakeClock::at(unlock_time(2024, 1));
+        let inputs = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let day_dir = repo.path().join("day-01");
+
+        let scaffolded = Cell::new(false);
+        fetch(
+            &client,
+            &clock,
+            inputs.path(),
+            2024,
+            1,
+            &day_dir,
+            || {
+                scaffolded.set(true);
+                fs::create_dir_all(&day_dir).unwrap();
+                Ok(())
+            },
+        )?;
+
+        locked.assert();
+        input.assert();
+        page.assert();
+        assert_eq!(1, clock.slept.get());
+        assert!(scaffolded.get());
+        assert_eq!(
+            "3   4\n",
+            fs::read_to_string(input::path(
+                inputs.path(),
+                2024,
+                1
+            ))
+            .unwrap()
+        );
+        assert!(
+            fs::read_to_string(day_dir.join("README.md"))
+                .unwrap()
+                .starts_with("# Day 1: Historian Hysteria")
+        );
+        assert!(
+            day_dir.join("examples/example-1.txt").exists()
+        );
+        let journal = journal::Journal::load(
+            &journal::path(inputs.path()),
+        )?;
+        assert!(
+            journal.get(2024, 1).unwrap().fetched.is_some()
+        );
+        Ok(())
+    }
+}
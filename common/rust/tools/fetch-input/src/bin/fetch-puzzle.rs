@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use aoc_core::input;
+use fetch_input::{
+    cli::{Site, check_days, parse_day},
+    puzzle,
+};
+use miette::miette;
+
+/// Downloads a day's puzzle description into the
+/// `README.md` of its crate, adding part two once
+/// it's unlocked, and saves its examples along
+/// with their expected answers in `examples/`.
+#[derive(clap::Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[clap(short, long)]
+    year: u16,
+    /// Either "5", "05" or "day-05".
+    #[clap(short, long, value_parser = parse_day)]
+    day: u8,
+    /// Crate to write the `README.md` into.
+    /// Defaults to `{year}/rust/day-{day}` in the
+    /// repository.
+    #[clap(long, value_name = "DIR")]
+    dir: Option<PathBuf>,
+    #[clap(flatten)]
+    site: Site,
+}
+
+fn main() -> miette::Result<()> {
+    use clap::Parser;
+
+    let args = Args::parse();
+    check_days(args.year, &(args.day..=args.day))
+        .map_err(|e| miette!("{e}"))?;
+
+    let dir = args.dir.unwrap_or_else(|| {
+        input::day_dir(args.year, args.day)
+    });
+    if !dir.is_dir() {
+        return Err(miette!(
+            help =
+                "Create the day first with `just create`.",
+            "{} doesn't exist",
+            dir.display()
+        ));
+    }
+
+    let (client, origin) = args.site.connect()?;
+    println!(
+        "Using session {} from {origin}",
+        client.session()
+    );
+    println!(
+        "Getting puzzle from `{}`",
+        client.puzzle_url(args.year, args.day)
+    );
+
+    let written = puzzle::download(
+        &client, args.year, args.day, &dir,
+    )?;
+    if written.is_empty() {
+        println!("{} is up to date", dir.display());
+    }
+    for path in &written {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
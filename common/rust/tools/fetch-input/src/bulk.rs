@@ -0,0 +1,156 @@
+use std::{fmt, path::Path};
+
+use aoc_core::input;
+
+use crate::{Client, FetchError, cli::days_in, journal};
+
+/// What happened to one day of a bulk fetch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Fetched,
+    /// Already downloaded, so not asked for.
+    Cached,
+    Locked,
+    /// Past the end of the year's calendar.
+    NoSuchDay,
+    Failed {
+        reason: String,
+    },
+}
+
+impl fmt::Display for Status {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Status::Fetched => f.write_str("fetched"),
+            Status::Cached => f.write_str("cached"),
+            Status::Locked => f.write_str("locked"),
+            Status::NoSuchDay => f.write_str("no such day"),
+            Status::Failed { reason } => {
+                write!(f, "failed: {reason}")
+            }
+        }
+    }
+}
+
+/// Downloads the inputs of `days` that aren't in
+/// `inputs` yet (or all of them with `force`),
+/// calling `progress` as each day is done.
+///
+/// A day the year doesn't have isn't asked for
+/// or counted as a failure, just
+/// [`Status::NoSuchDay`].
+///
+/// Days unlock in order, so once one is locked
+/// the rest aren't asked for. An expired session
+/// or the site asking to slow down fails every
+/// remaining day without asking either.
+pub fn fetch_days(
+    client: &Client,
+    inputs: &Path,
+    year: u16,
+    days: impl IntoIterator<Item = u8>,
+    force: bool,
+    mut progress: impl FnMut(u8, &Status),
+) -> Vec<(u8, Status)> {
+    let mut stop: Option<Status> = None;
+    let mut statuses = vec![];
+    for day in days {
+        let path = input::path(inputs, year, day);
+        let status = if !days_in(year).contains(&day) {
+            Status::NoSuchDay
+        } else if path.exists() && !force {
+            Status::Cached
+        } else if let Some(status) = &stop {
+            status.clone()
+        } else {
+            let fetched = client
+                .download_input(year, day, &path)
+                .and_then(|()| {
+                    journal::record_fetched(
+                        inputs, year, day,
+                    )
+                });
+            match fetched {
+                Ok(()) => Status::Fetched,
+                Err(FetchError::NotUnlocked { .. }) => {
+                    stop = Some(Status::Locked);
+                    Status::Locked
+                }
+                Err(
+                    e @ (FetchError::ExpiredSession
+                    | FetchError::RateLimited { .. }),
+                ) => {
+                    stop = Some(Status::Failed {
+                        reason: format!("skipped, {e}"),
+                    });
+                    Status::Failed {
+                        reason: e.to_string(),
+                    }
+                }
+                Err(e) => Status::Failed {
+                    reason: e.to_string(),
+                },
+            }
+        };
+        progress(day, &status);
+        statuses.push((day, status));
+    }
+    statuses
+}
+
+/// How many days ended up in each state, e.g.
+/// "3 fetched, 20 cached, 2 locked, 0 failed",
+/// with days the year doesn't have only counted
+/// when there are any.
+pub fn summary(statuses: &[(u8, Status)]) -> String {
+    let count = |matches: fn(&Status) -> bool| {
+        statuses
+            .iter()
+            .filter(|(_, status)| matches(status))
+            .count()
+    };
+    let no_such_day =
+        match count(|s| *s == Status::NoSuchDay) {
+            0 => String::new(),
+            n => format!(", {n} no such day"),
+        };
+    format!(
+        "{} fetched, {} cached, {} locked{no_such_day}, {} failed",
+        count(|s| *s == Status::Fetched),
+        count(|s| *s == Status::Cached),
+        count(|s| *s == Status::Locked),
+        count(|s| matches!(s, Status::Failed { .. })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let statuses = [
+            (1, Status::Cached),
+            (2, Status::Fetched),
+            (
+                3,
+                Status::Failed {
+                    reason: "oops".to_string(),
+                },
+            ),
+            (4, Status::Locked),
+            (5, Status::Locked),
+        ];
+        assert_eq!(
+            "1 fetched, 1 cached, 2 locked, 1 failed",
+            summary(&statuses)
+        );
+        assert_eq!(
+            "0 fetched, 0 cached, 0 locked, 1 no such day, 0 failed",
+            summary(&[(13, Status::NoSuchDay)])
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::ops::RangeInclusive;
+
+use nom::{
+    IResult, Parser,
+    bytes::complete::tag,
+    character::complete,
+    combinator::opt,
+    sequence::{preceded, separated_pair},
+};
+
+use crate::{
+    Client,
+    client::DEFAULT_BASE_URL,
+    config::{self, Config},
+    error::FetchError,
+    http::{Http, Settings},
+    session::{Lookup, Origin},
+};
+
+/// Options shared by every tool that talks to the
+/// site.
+#[derive(clap::Args, Debug)]
+pub struct Site {
+    /// Profile from the config file to take the
+    /// session token from.
+    #[clap(short, long, env = "AOC_PROFILE")]
+    pub profile: Option<String>,
+    /// Site to download from. Defaults to the
+    /// config file's `base_url`, or
+    /// adventofcode.com.
+    #[clap(long, env = "AOC_BASE_URL", value_name = "URL")]
+    pub base_url: Option<String>,
+    /// How the site's operators can reach you
+    /// (an email address or repository URL), sent
+    /// in the User-Agent. Defaults to the config
+    /// file's `contact`.
+    #[clap(long, env = "AOC_CONTACT")]
+    pub contact: Option<String>,
+}
+
+impl Site {
+    /// Finds a session token and builds a client
+    /// for the chosen site.
+    pub fn connect(
+        &self,
+    ) -> Result<(Client, Origin), FetchError> {
+        let (session, origin) =
+            Lookup::from_env(self.profile.as_deref())
+                .resolve()?;
+        // Like the session, the config file is only
+        // read when it's needed.
+        let config = if self.base_url.is_some()
+            && self.contact.is_some()
+        {
+            Config::default()
+        } else {
+            config::default_path()
+                .map(|path| Config::load(&path))
+                .transpose()?
+                .unwrap_or_default()
+        };
+        let base_url = self
+            .base_url
+            .clone()
+            .or(config.base_url)
+            .unwrap_or_else(|| {
+                DEFAULT_BASE_URL.to_string()
+            });
+        let contact =
+            self.contact.clone().or(config.contact);
+        if contact.is_none() {
+            eprintln!(
+                "Set `contact` in the config file or `AOC_CONTACT` \
+                 so the site's operators can reach you."
+            );
+        }
+
+        let http = Http::new(Settings {
+            contact,
+            ..Settings::default()
+        });
+        Ok((
+            Client::with_http(base_url, session, http),
+            origin,
+        ))
+    }
+}
+
+/// Most days any year has had.
+const MAX_DAYS: u8 = 25;
+
+/// The days a year's calendar has: 25 until
+/// 2024, 12 from 2025 on.
+pub fn days_in(year: u16) -> RangeInclusive<u8> {
+    if year < 2025 { 1..=MAX_DAYS } else { 1..=12 }
+}
+
+/// Parses a day formatted as "5", "05" or
+/// "day-05". Every tool takes days this way.
+pub fn parse_day(input: &str) -> Result<u8, String> {
+    fn day(input: &str) -> IResult<&str, u8> {
+        preceded(opt(tag("day-")), complete::u8)
+            .parse(input)
+    }
+
+    match day(input) {
+        Ok(("", day)) if (1..=MAX_DAYS).contains(&day) => {
+            Ok(day)
+        }
+        _ => Err(format!(
+            "Day `{input}` must be formatted as `5`, `05` \
+             or `day-05`, between 1 and {MAX_DAYS}"
+        )),
+    }
+}
+
+/// Parses a range of days formatted as "1..=10".
+pub fn parse_days(
+    input: &str,
+) -> Result<RangeInclusive<u8>, String> {
+    fn days(input: &str) -> IResult<&str, (u8, u8)> {
+        separated_pair(
+            complete::u8,
+            tag("..="),
+            complete::u8,
+        )
+        .parse(input)
+    }
+
+    match days(input) {
+        Ok(("", (first, last)))
+            if 1 <= first
+                && first <= last
+                && last <= MAX_DAYS =>
+        {
+            Ok(first..=last)
+        }
+        _ => Err(format!(
+            "Days `{input}` must be formatted as `1..=10`, \
+             between 1 and {MAX_DAYS}"
+        )),
+    }
+}
+
+/// Checks that `year`'s calendar has all of
+/// `days`, which [`parse_day`] and [`parse_days`]
+/// can't know without the year.
+pub fn check_days(
+    year: u16,
+    days: &RangeInclusive<u8>,
+) -> Result<(), String> {
+    let calendar = days_in(year);
+    if calendar.contains(days.start())
+        && calendar.contains(days.end())
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "{year} only has days {} to {}",
+            calendar.start(),
+            calendar.end()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(2015, 25)]
+    #[case(2024, 25)]
+    #[case(2025, 12)]
+    fn test_days_in(#[case] year: u16, #[case] last: u8) {
+        assert_eq!(1..=last, days_in(year));
+    }
+
+    #[rstest]
+    #[case("5", Ok(5))]
+    #[case("05", Ok(5))]
+    #[case("day-05", Ok(5))]
+    #[case("day-25", Ok(25))]
+    #[case("0", Err(()))]
+    #[case("26", Err(()))]
+    #[case("day-26", Err(()))]
+    #[case("day-05x", Err(()))]
+    #[case("five", Err(()))]
+    fn test_parse_day(
+        #[case] input: &str,
+        #[case] expected: Result<u8, ()>,
+    ) {
+        assert_eq!(
+            expected,
+            parse_day(input).map_err(|_| ())
+        );
+    }
+
+    #[rstest]
+    #[case(2024, 1..=25, true)]
+    #[case(2025, 1..=12, true)]
+    #[case(2025, 5..=5, true)]
+    #[case(2025, 1..=25, false)]
+    #[case(2025, 13..=13, false)]
+    fn test_check_days(
+        #[case] year: u16,
+        #[case] days: RangeInclusive<u8>,
+        #[case] ok: bool,
+    ) {
+        assert_eq!(ok, check_days(year, &days).is_ok());
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(Ok(1..=10), parse_days("1..=10"));
+        assert_eq!(Ok(25..=25), parse_days("25..=25"));
+        assert!(parse_days("0..=10").is_err());
+        assert!(parse_days("1..=26").is_err());
+        assert!(parse_days("10..=1").is_err());
+        assert!(parse_days("1..10").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fs, io, path::Path};
+
+use aoc_core::Part;
+use reqwest::header::COOKIE;
+
+use crate::{
+    error::FetchError, http::Http, response,
+    session::Session, submit::Verdict,
+};
+
+pub const DEFAULT_BASE_URL: &str =
+    "https://adventofcode.com";
+
+/// Logged-in client for a single Advent of Code
+/// site.
+pub struct Client {
+    http: Http,
+    base_url: String,
+    session: Session,
+}
+
+impl Client {
+    /// A client with the default [`Http`]
+    /// settings.
+    pub fn new(
+        base_url: impl Into<String>,
+        session: Session,
+    ) -> Self {
+        Client::with_http(
+            base_url,
+            session,
+            Http::default(),
+        )
+    }
+
+    pub fn with_http(
+        base_url: impl Into<String>,
+        session: Session,
+        http: Http,
+    ) -> Self {
+        let base_url = base_url.into();
+        Client {
+            http,
+            base_url: base_url
+                .trim_end_matches('/')
+                .to_string(),
+            session,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn puzzle_url(&self, year: u16, day: u8) -> String {
+        format!("{}/{year}/day/{day}", self.base_url)
+    }
+
+    pub fn input_url(&self, year: u16, day: u8) -> String {
+        format!("{}/input", self.puzzle_url(year, day))
+    }
+
+    pub fn answer_url(&self, year: u16, day: u8) -> String {
+        format!("{}/answer", self.puzzle_url(year, day))
+    }
+
+    pub fn leaderboard_url(
+        &self,
+        year: u16,
+        id: u64,
+    ) -> String {
+        format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        )
+    }
+
+    /// Downloads a private leaderboard's JSON.
+    /// Mind [`leaderboard::MIN_REFRESH`]; use
+    /// [`leaderboard::load`] to cache it.
+    ///
+    /// [`leaderboard::MIN_REFRESH`]: crate::leaderboard::MIN_REFRESH
+    /// [`leaderboard::load`]: crate::leaderboard::load
+    pub fn leaderboard_json(
+        &self,
+        year: u16,
+        id: u64,
+    ) -> Result<String, FetchError> {
+        let url = self.leaderboard_url(year, id);
+        let (status, body) = self.get(&url)?;
+        response::check_leaderboard(
+            &url, year, id, status, body,
+        )
+    }
+
+    /// Downloads a day's input, failing on
+    /// anything that isn't one.
+    pub fn input(
+        &self,
+        year: u16,
+        day: u8,
+    ) -> Result<String, FetchError> {
+        let url = self.input_url(year, day);
+        let (status, body) = self.get(&url)?;
+        response::check_input(&url, year, day, status, body)
+    }
+
+    /// Downloads a day's puzzle page, which only
+    /// has part two once part one is solved.
+    pub fn puzzle_page(
+        &self,
+        year: u16,
+        day: u8,
+    ) -> Result<String, FetchError> {
+        let url = self.puzzle_url(year, day);
+        let (status, body) = self.get(&url)?;
+        response::check_page(&url, year, day, status, body)
+    }
+
+    /// Submits an answer to one part of a day.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Verdict, FetchError> {
+        let url = self.answer_url(year, day);
+        let level = part.to_string();
+        let (status, body) =
+            self.http.send(&url, |http| {
+                http.post(&url)
+                    .header(COOKIE, self.session.cookie())
+                    .form(&[
+                        ("level", level.as_str()),
+                        ("answer", answer),
+                    ])
+            })?;
+        response::check_answer(
+            &url, year, day, status, body,
+        )
+    }
+
+    fn get(
+        &self,
+        url: &str,
+    ) -> Result<(u16, String), FetchError> {
+        self.http.send(url, |http| {
+            http.get(url)
+                .header(COOKIE, self.session.cookie())
+        })
+    }
+
+    /// Downloads a day's input into `path`.
+    /// Whatever was there is only replaced
+    /// once the whole input has arrived.
+    pub fn download_input(
+        &self,
+        year: u16,
+        day: u8,
+        path: &Path,
+    ) -> Result<(), FetchError> {
+        let input = self.input(year, day)?;
+        write_atomically(path, &input).map_err(|source| {
+            FetchError::Write {
+                path: path.to_path_buf(),
+                source,
+            }
+        })
+    }
+}
+
+/// Writes next to `path` first and renames it
+/// into place, so an interrupted write never
+/// replaces a good input with half of one.
+pub fn write_atomically(
+    path: &Path,
+    contents: &str,
+) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    fs::write(&partial, contents)?;
+    fs::rename(&partial, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_url() {
+        let client = Client::new(
+            "http://127.0.0.1:1234/",
+            Session::new("token"),
+        );
+        assert_eq!(
+            "http://127.0.0.1:1234/2024/day/5/input",
+            client.input_url(2024, 5)
+        );
+    }
+
+    #[test]
+    fn test_write_atomically_replaces_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path =
+            dir.path().join("2024").join("day-05.txt");
+        write_atomically(&path, "old").unwrap();
+        write_atomically(&path, "new").unwrap();
+
+        assert_eq!(
+            "new",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert!(
+            !path.with_extension("txt.partial").exists()
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use miette::NamedSource;
+use serde::Deserialize;
+
+use crate::{error::FetchError, session::Session};
+
+/// Overrides the location of the config file.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+/// Per-user settings, kept out of the repository
+/// in `~/.config/aoc/config.toml`:
+///
+/// ```toml
+/// default_profile = "personal"
+/// # Only needed to talk to something other than
+/// # adventofcode.com.
+/// base_url = "http://localhost:8080"
+/// # Sent in the User-Agent of every request.
+/// contact = "you@example.com"
+/// # Private leaderboard shown by
+/// # `aoc leaderboard`.
+/// leaderboard = 123456
+///
+/// [profiles.personal]
+/// session = "53616c7465645f5f..."
+///
+/// [profiles.work]
+/// session = "53616c7465645f5f..."
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub default_profile: Option<String>,
+    pub base_url: Option<String>,
+    pub contact: Option<String>,
+    pub leaderboard: Option<u64>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub session: Session,
+}
+
+/// `$AOC_CONFIG`, or `aoc/config.toml` in the
+/// user's config directory.
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os(CONFIG_ENV).map(PathBuf::from).or_else(
+        || {
+            dirs::config_dir().map(|dir| {
+                dir.join("aoc").join("config.toml")
+            })
+        },
+    )
+}
+
+impl Config {
+    /// Reads the config at `path`, which is fine
+    /// to not exist.
+    pub fn load(path: &Path) -> Result<Config, FetchError> {
+        let src = match std::fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e)
+                if e.kind() == io::ErrorKind::NotFound =>
+            {
+                return Ok(Config::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Config::parse(&path.display().to_string(), src)
+    }
+
+    fn parse(
+        name: &str,
+        src: String,
+    ) -> Result<Config, FetchError> {
+        toml::from_str(&src).map_err(|e| {
+            FetchError::InvalidConfig {
+                span: e.span().map(Into::into),
+                message: e.message().to_string(),
+                src: NamedSource::new(name, src),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let config = Config::parse(
+            "config.toml",
+            r#"
+default_profile = "work"
+
+[profiles.work]
+session = "cafebabe"
+"#
+            .to_string(),
+        )?;
+        assert_eq!(
+            Some("work"),
+            config.default_profile.as_deref()
+        );
+        assert_eq!(
+            "session=cafebabe",
+            config.profiles["work"].session.cookie()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_points_at_mistakes() {
+        let err = Config::parse(
+            "config.toml",
+            "[profiles.work]\ntoken = \"cafebabe\"\n"
+                .to_string(),
+        )
+        .unwrap_err();
+        let FetchError::InvalidConfig {
+            span, message, ..
+        } = err
+        else {
+            panic!("expected an invalid config error");
+        };
+        assert_eq!(Some(16), span.map(|s| s.offset()));
+        assert!(message.contains("token"));
+    }
+
+    #[test]
+    fn test_load_missing_file() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let config =
+            Config::load(&dir.path().join("config.toml"))?;
+        assert!(config.profiles.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use thiserror::Error;
+
+use crate::history::Outcome;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum FetchError {
+    #[error(transparent)]
+    #[diagnostic(code(fetch_input::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("Request to {url} failed")]
+    #[diagnostic(code(fetch_input::request))]
+    Request {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("Failed to write {}", path.display())]
+    #[diagnostic(code(fetch_input::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("No session token found")]
+    #[diagnostic(code(fetch_input::missing_session))]
+    MissingSession {
+        #[help]
+        help: String,
+    },
+
+    #[error("No `{profile}` profile in {}", path.display())]
+    #[diagnostic(code(fetch_input::unknown_profile))]
+    UnknownProfile {
+        profile: String,
+        path: PathBuf,
+        #[help]
+        help: String,
+    },
+
+    #[error("The session cookie was rejected")]
+    #[diagnostic(
+        code(fetch_input::expired_session),
+        help(
+            "Sessions expire after about a month; log in \
+             to adventofcode.com again and copy the new \
+             `session` cookie."
+        )
+    )]
+    ExpiredSession,
+
+    #[error("{year} day {day} isn't unlocked yet")]
+    #[diagnostic(
+        code(fetch_input::not_unlocked),
+        help(
+            "Puzzles unlock at midnight US Eastern time; \
+             try again once the countdown is over."
+        )
+    )]
+    NotUnlocked { year: u16, day: u8 },
+
+    #[error("Too many requests to {url}")]
+    #[diagnostic(
+        code(fetch_input::rate_limited),
+        help("Wait a few minutes before trying again.")
+    )]
+    RateLimited { url: String },
+
+    #[error("No puzzle description found at {url}")]
+    #[diagnostic(code(fetch_input::no_description))]
+    NoDescription { url: String },
+
+    #[error("There is no puzzle at {url}")]
+    #[diagnostic(code(fetch_input::not_found))]
+    NotFound { url: String },
+
+    #[error("Unexpected response ({status}) from {url}")]
+    #[diagnostic(code(fetch_input::unexpected_response))]
+    UnexpectedResponse {
+        url: String,
+        status: u16,
+        #[help]
+        excerpt: String,
+    },
+
+    #[error(
+        "There is no private leaderboard {id} in {year}"
+    )]
+    #[diagnostic(
+        code(fetch_input::no_leaderboard),
+        help(
+            "The id is the number at the end of the \
+             leaderboard's URL."
+        )
+    )]
+    NoLeaderboard { year: u16, id: u64 },
+
+    #[error("{url} isn't a leaderboard")]
+    #[diagnostic(
+        code(fetch_input::invalid_leaderboard),
+        help(
+            "Check the leaderboard's id and that the \
+             session's account is a member of it ({message})."
+        )
+    )]
+    InvalidLeaderboard { url: String, message: String },
+
+    #[error("Invalid config file")]
+    #[diagnostic(code(fetch_input::invalid_config))]
+    InvalidConfig {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("{message}")]
+        span: Option<SourceSpan>,
+        message: String,
+    },
+
+    #[error("Invalid answer history")]
+    #[diagnostic(code(fetch_input::invalid_history))]
+    InvalidHistory {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("{message}")]
+        span: Option<SourceSpan>,
+        message: String,
+    },
+
+    #[error("Invalid solve-time journal")]
+    #[diagnostic(code(fetch_input::invalid_journal))]
+    InvalidJournal {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("{message}")]
+        span: Option<SourceSpan>,
+        message: String,
+    },
+
+    #[error("Part {part} is already solved")]
+    #[diagnostic(
+        code(fetch_input::already_solved),
+        help("The accepted answer was `{solution}`.")
+    )]
+    AlreadySolved { part: u8, solution: String },
+
+    #[error("`{answer}` was already submitted")]
+    #[diagnostic(
+        code(fetch_input::known_wrong),
+        help("The site said it was {outcome}.")
+    )]
+    KnownWrong { answer: String, outcome: Outcome },
+
+    #[error("`{answer}` can't be right")]
+    #[diagnostic(code(fetch_input::out_of_bounds))]
+    OutOfBounds {
+        answer: String,
+        #[help]
+        help: String,
+    },
+
+    #[error("The site's answer to {url} wasn't understood")]
+    #[diagnostic(code(fetch_input::unknown_verdict))]
+    UnknownVerdict {
+        url: String,
+        #[help]
+        excerpt: String,
+    },
+}
@@ -0,0 +1,249 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::{Part, examples::answer_path};
+use scraper::{ElementRef, Html, Selector};
+
+use crate::client::write_atomically;
+
+/// A `<pre><code>` block from a puzzle page along
+/// with the answers the page gives for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub input: String,
+    /// Expected answer of each part, when the
+    /// page says what it is.
+    pub answers: [Option<String>; 2],
+}
+
+/// Pulls the examples out of a puzzle page.
+///
+/// A block counts as an example when the
+/// paragraph right before it mentions one ("For
+/// example:"), which leaves out the intermediate
+/// states many puzzles draw; a part without any
+/// such block falls back to its first one. The
+/// last emphasised `<code>` of each part is taken
+/// as that part's answer, and given to the latest
+/// example seen so far, since part two usually
+/// reuses part one's.
+pub fn extract(html: &str) -> Vec<Example> {
+    let html = Html::parse_document(html);
+    let articles = Selector::parse("article.day-desc")
+        .expect("valid selector");
+    let answers = Selector::parse("code > em, em > code")
+        .expect("valid selector");
+
+    let mut examples: Vec<Example> = vec![];
+    for (part, article) in
+        html.select(&articles).enumerate().take(2)
+    {
+        examples.extend(blocks(article).into_iter().map(
+            |input| Example {
+                input,
+                answers: [None, None],
+            },
+        ));
+
+        let answer =
+            article.select(&answers).last().map(|e| {
+                e.text()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            });
+        if let Some(example) = examples.last_mut() {
+            example.answers[part] = answer;
+        }
+    }
+    examples
+}
+
+fn blocks(article: ElementRef<'_>) -> Vec<String> {
+    let mut all = vec![];
+    let mut examples = vec![];
+    let mut previous = String::new();
+    for element in article.child_elements() {
+        match element.value().name() {
+            "pre" => {
+                let input =
+                    element.text().collect::<String>();
+                if previous
+                    .to_lowercase()
+                    .contains("example")
+                {
+                    examples.push(input.clone());
+                }
+                all.push(input);
+            }
+            "p" => previous = element.text().collect(),
+            _ => previous.clear(),
+        }
+    }
+    if examples.is_empty() {
+        examples.extend(all.into_iter().take(1));
+    }
+    examples
+}
+
+/// Path of the `n`th example (counting from 1)
+/// and of its expected answer for `part`.
+pub fn paths(
+    dir: &Path,
+    n: usize,
+    part: Part,
+) -> (PathBuf, PathBuf) {
+    let input = dir.join(format!("example-{n}.txt"));
+    let answer = answer_path(&input, part);
+    (input, answer)
+}
+
+/// Saves examples as `example-N.txt`, next to an
+/// `example-N.partP.answer` for every known
+/// answer. Files that already exist are kept as
+/// they are, so hand-made fixes survive fetching
+/// the page again. Returns the files that were
+/// written.
+pub fn save(
+    dir: &Path,
+    examples: &[Example],
+) -> io::Result<Vec<PathBuf>> {
+    let mut written = vec![];
+    for (i, example) in examples.iter().enumerate() {
+        for (part, answer) in
+            Part::ALL.into_iter().zip(&example.answers)
+        {
+            let (input, expected) = paths(dir, i + 1, part);
+            if !input.exists() {
+                write_atomically(&input, &example.input)?;
+                written.push(input);
+            }
+            if let Some(answer) = answer
+                && !expected.exists()
+            {
+                write_atomically(
+                    &expected,
+                    &format!("{answer}\n"),
+                )?;
+                written.push(expected);
+            }
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::examples::load_dir;
+
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html><html><body><main>
+<article class="day-desc"><h2>--- Day 4: Ceres Search ---</h2>
+<p>Here are a few ways XMAS might appear:</p>
+<pre><code>..X...
+.SAMX.
+</code></pre>
+<p>Take a look at the following larger example:</p>
+<pre><code>MMMSXXMASM
+MSAMXMSMSA
+</code></pre>
+<p>Here's the same word search again, but where the letters not involved have been replaced with <code>.</code>:</p>
+<pre><code>....XXMAS.
+.SAMXMS...
+</code></pre>
+<p>XMAS occurs a total of <code><em>18</em></code> times.</p>
+</article>
+<p>Your puzzle answer was <code>2500</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Here's the same example from before:</p>
+<pre><code>.M.S......
+..A..MSMS.
+</code></pre>
+<p>In this example, an X-MAS appears <code><em>9</em></code> times.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            vec![
+                Example {
+                    input: "MMMSXXMASM\nMSAMXMSMSA\n"
+                        .to_string(),
+                    answers: [Some("18".to_string()), None],
+                },
+                Example {
+                    input: ".M.S......\n..A..MSMS.\n"
+                        .to_string(),
+                    answers: [None, Some("9".to_string())],
+                },
+            ],
+            extract(PAGE)
+        );
+    }
+
+    #[test]
+    fn test_extract_reuses_part_one_example() {
+        let page = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>A total distance of <code><em>11</em></code>!</p>
+</article>
+<article class="day-desc"><h2>--- Part Two ---</h2>
+<p>So, for these example lists, the similarity score is <code><em>31</em></code>.</p>
+</article></main>"#;
+        assert_eq!(
+            vec![Example {
+                input: "3   4\n4   3\n".to_string(),
+                answers: [
+                    Some("11".to_string()),
+                    Some("31".to_string())
+                ],
+            }],
+            extract(page)
+        );
+    }
+
+    #[test]
+    fn test_save_keeps_existing_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let examples = vec![Example {
+            input: "3   4\n".to_string(),
+            answers: [Some("11".to_string()), None],
+        }];
+
+        let written = save(dir.path(), &examples)?;
+        assert_eq!(2, written.len());
+        let (input, _) = paths(dir.path(), 1, Part::One);
+        assert_eq!(
+            [Some("11".to_string()), None],
+            load_dir(dir.path())?[0].answers
+        );
+
+        fs::write(&input, "fixed by hand\n")?;
+        let examples = vec![Example {
+            answers: [
+                Some("11".to_string()),
+                Some("31".to_string()),
+            ],
+            ..examples[0].clone()
+        }];
+        let written = save(dir.path(), &examples)?;
+        assert_eq!(
+            vec![paths(dir.path(), 1, Part::Two).1],
+            written
+        );
+        assert_eq!(
+            "fixed by hand\n",
+            fs::read_to_string(&input)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::Part;
+use miette::NamedSource;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::write_atomically,
+    error::FetchError,
+    submit::{Hint, Verdict},
+};
+
+/// Every answer submitted for a day, kept next to
+/// its input as
+/// `{year}/day-{day:02}.history.toml`:
+///
+/// ```toml
+/// [[attempt]]
+/// part = 1
+/// answer = "4521"
+/// outcome = "too-high"
+/// at = 1733375412
+/// ```
+#[derive(
+    Debug, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(deny_unknown_fields)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(deny_unknown_fields)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Not checked, because the previous answer
+    /// was too recent.
+    TooSoon,
+    /// Not checked, because the part wasn't open
+    /// for answers.
+    WrongLevel,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooSoon => "too soon to check",
+            Outcome::WrongLevel => "for the wrong level",
+        })
+    }
+}
+
+impl From<&Verdict> for Outcome {
+    fn from(verdict: &Verdict) -> Self {
+        match verdict {
+            Verdict::Correct => Outcome::Correct,
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => Outcome::TooHigh,
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+                ..
+            } => Outcome::TooLow,
+            Verdict::Wrong { hint: None, .. } => {
+                Outcome::Wrong
+            }
+            Verdict::TooSoon { .. } => Outcome::TooSoon,
+            Verdict::WrongLevel => Outcome::WrongLevel,
+        }
+    }
+}
+
+/// Path of a day's history inside an inputs
+/// directory.
+pub fn path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day-{day:02}.history.toml"))
+}
+
+impl History {
+    /// Reads the history at `path`, which is fine
+    /// to not exist yet.
+    pub fn load(
+        path: &Path,
+    ) -> Result<History, FetchError> {
+        let src = match std::fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e)
+                if e.kind() == io::ErrorKind::NotFound =>
+            {
+                return Ok(History::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        History::parse(&path.display().to_string(), src)
+    }
+
+    fn parse(
+        name: &str,
+        src: String,
+    ) -> Result<History, FetchError> {
+        toml::from_str(&src).map_err(|e| {
+            FetchError::InvalidHistory {
+                span: e.span().map(Into::into),
+                message: e.message().to_string(),
+                src: NamedSource::new(name, src),
+            }
+        })
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> Result<(), FetchError> {
+        let contents = toml::to_string(self)
+            .expect("history serializes to TOML");
+        write_atomically(path, &contents).map_err(
+            |source| FetchError::Write {
+                path: path.to_path_buf(),
+                source,
+            },
+        )
+    }
+
+    pub fn record(
+        &mut self,
+        part: Part,
+        answer: &str,
+        verdict: &Verdict,
+        at: u64,
+    ) {
+        self.attempts.push(Attempt {
+            part: part.number(),
+            answer: answer.to_string(),
+            outcome: verdict.into(),
+            at,
+        });
+    }
+
+    fn judged(
+        &self,
+        part: Part,
+    ) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |attempt| {
+            attempt.part == part.number()
+                && !matches!(
+                    attempt.outcome,
+                    Outcome::TooSoon | Outcome::WrongLevel
+                )
+        })
+    }
+
+    /// The answer already accepted for `part`.
+    pub fn solution(&self, part: Part) -> Option<&str> {
+        self.judged(part)
+            .find(|attempt| {
+                attempt.outcome == Outcome::Correct
+            })
+            .map(|attempt| attempt.answer.as_str())
+    }
+
+    /// Highest answer known to be too low and
+    /// lowest known to be too high.
+    pub fn bounds(
+        &self,
+        part: Part,
+    ) -> (Option<i128>, Option<i128>) {
+        let numeric = |outcome| {
+            self.judged(part)
+                .filter(move |attempt| {
+                    attempt.outcome == outcome
+                })
+                .filter_map(|attempt| {
+                    attempt.answer.parse::<i128>().ok()
+                })
+        };
+        (
+            numeric(Outcome::TooLow).max(),
+            numeric(Outcome::TooHigh).min(),
+        )
+    }
+
+    /// Refuses answers that would only waste an
+    /// attempt: ones already given, and numbers
+    /// outside the bounds earlier attempts set.
+    pub fn check(
+        &self,
+        part: Part,
+        answer: &str,
+    ) -> Result<(), FetchError> {
+        if let Some(solution) = self.solution(part) {
+            return Err(FetchError::AlreadySolved {
+                part: part.number(),
+                solution: solution.to_string(),
+            });
+        }
+
+        if let Some(attempt) = self
+            .judged(part)
+            .find(|attempt| attempt.answer == answer)
+        {
+            return Err(FetchError::KnownWrong {
+                answer: answer.to_string(),
+                outcome: attempt.outcome,
+            });
+        }
+
+        let Ok(number) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        match self.bounds(part) {
+            (Some(low), _) if number <= low => {
+                Err(FetchError::OutOfBounds {
+                    answer: answer.to_string(),
+                    help: format!(
+                        "{low} was already too low."
+                    ),
+                })
+            }
+            (_, Some(high)) if number >= high => {
+                Err(FetchError::OutOfBounds {
+                    answer: answer.to_string(),
+                    help: format!(
+                        "{high} was already too high."
+                    ),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn history() -> History {
+        let mut history = History::default();
+        let wrong =
+            |hint| Verdict::Wrong { hint, wait: None };
+        history.record(
+            Part::One,
+            "100",
+            &wrong(Some(Hint::TooLow)),
+            1,
+        );
+        history.record(
+            Part::One,
+            "500",
+            &wrong(Some(Hint::TooHigh)),
+            2,
+        );
+        history.record(
+            Part::One,
+            "200",
+            &wrong(Some(Hint::TooLow)),
+            3,
+        );
+        history.record(Part::One, "abc", &wrong(None), 4);
+        history.record(
+            Part::One,
+            "300",
+            &Verdict::TooSoon {
+                wait: std::time::Duration::from_secs(30),
+            },
+            5,
+        );
+        history
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(
+            (Some(200), Some(500)),
+            history().bounds(Part::One)
+        );
+        assert_eq!(
+            (None, None),
+            history().bounds(Part::Two)
+        );
+    }
+
+    #[rstest]
+    #[case("300")]
+    #[case("499")]
+    #[case("201")]
+    #[case("xyz")]
+    fn test_check_allows(#[case] answer: &str) {
+        assert!(history().check(Part::One, answer).is_ok());
+    }
+
+    #[rstest]
+    #[case("100")]
+    #[case("150")]
+    #[case("200")]
+    #[case("500")]
+    #[case("9000")]
+    #[case("abc")]
+    fn test_check_refuses(#[case] answer: &str) {
+        assert!(
+            history().check(Part::One, answer).is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_solved_part() {
+        let mut history = history();
+        history.record(
+            Part::One,
+            "300",
+            &Verdict::Correct,
+            6,
+        );
+        assert!(matches!(
+            history.check(Part::One, "301"),
+            Err(FetchError::AlreadySolved { part: 1, .. })
+        ));
+        assert!(history.check(Part::Two, "100").is_ok());
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = path(dir.path(), 2024, 5);
+        assert_eq!(
+            History::default(),
+            History::load(&path)?
+        );
+
+        history().save(&path)?;
+        assert_eq!(history(), History::load(&path)?);
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("outcome = \"too-high\"")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::RETRY_AFTER,
+};
+
+use crate::error::FetchError;
+
+/// How politely to talk to the site.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Put in the `User-Agent` so the site's
+    /// operators can get in touch, e.g. an email
+    /// address or a repository URL.
+    pub contact: Option<String>,
+    /// Shortest time between two requests, across
+    /// every tool using the same `state` file.
+    pub min_interval: Duration,
+    /// File remembering when the last request was
+    /// made, so separate runs are throttled too.
+    pub state: Option<PathBuf>,
+    /// How many times a request is repeated after
+    /// a 429 or 5xx response.
+    pub retries: u32,
+    /// Wait before the first retry, doubled for
+    /// every further one.
+    pub backoff: Duration,
+    /// Upper bound for a single wait, including
+    /// one asked for by `Retry-After`.
+    pub max_wait: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            contact: None,
+            min_interval: Duration::from_secs(3),
+            state: default_state_path(),
+            retries: 3,
+            backoff: Duration::from_secs(5),
+            max_wait: Duration::from_secs(120),
+        }
+    }
+}
+
+/// `aoc/last-request` in the user's cache
+/// directory.
+pub fn default_state_path() -> Option<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("aoc").join("last-request"))
+}
+
+/// The `User-Agent` sent with every request.
+pub fn user_agent(contact: Option<&str>) -> String {
+    let agent = concat!(
+        env!("CARGO_PKG_NAME"),
+        "/",
+        env!("CARGO_PKG_VERSION")
+    );
+    match contact {
+        Some(contact) => format!("{agent} ({contact})"),
+        None => agent.to_string(),
+    }
+}
+
+/// HTTP client every request to the site goes
+/// through: it identifies itself, keeps requests
+/// apart and backs off when the site is busy.
+#[derive(Debug)]
+pub struct Http {
+    client: Client,
+    settings: Settings,
+    last: Mutex<Option<SystemTime>>,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Http::new(Settings::default())
+    }
+}
+
+impl Http {
+    pub fn new(settings: Settings) -> Self {
+        let client = Client::builder()
+            .user_agent(user_agent(
+                settings.contact.as_deref(),
+            ))
+            .build()
+            .expect("a client without custom TLS builds");
+        Http {
+            client,
+            settings,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Sends the request `build` makes, once the
+    /// minimum interval has passed, repeating it
+    /// while the site answers with 429 or 5xx.
+    /// Returns the status and body of the last
+    /// response.
+    pub fn send(
+        &self,
+        url: &str,
+        build: impl Fn(&Client) -> RequestBuilder,
+    ) -> Result<(u16, String), FetchError> {
+        let request_failed = |source| FetchError::Request {
+            url: url.to_string(),
+            source,
+        };
+
+        let mut attempt = 0;
+        loop {
+            self.throttle();
+            let response = build(&self.client)
+                .send()
+                .map_err(request_failed)?;
+            let status = response.status();
+            let retryable = status.as_u16() == 429
+                || status.is_server_error();
+            if retryable && attempt < self.settings.retries
+            {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| {
+                        value.trim().parse().ok()
+                    })
+                    .map(Duration::from_secs);
+                thread::sleep(backoff(
+                    &self.settings,
+                    attempt,
+                    retry_after,
+                ));
+                attempt += 1;
+                continue;
+            }
+
+            let body =
+                response.text().map_err(request_failed)?;
+            return Ok((status.as_u16(), body));
+        }
+    }
+
+    /// Waits until `min_interval` has passed
+    /// since the last request made by this or
+    /// any other run, and marks now as the
+    /// last request. Trouble with the state
+    /// file only loses the throttling across
+    /// runs, so it's ignored.
+    fn throttle(&self) {
+        let mut last =
+            self.last.lock().unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            });
+        let stored =
+            self.settings.state.as_ref().and_then(|path| {
+                let millis =
+                    fs::read_to_string(path).ok()?;
+                let millis: u64 =
+                    millis.trim().parse().ok()?;
+                Some(
+                    UNIX_EPOCH
+                        + Duration::from_millis(millis),
+                )
+            });
+
+        let wait = delay(
+            (*last).max(stored),
+            SystemTime::now(),
+            self.settings.min_interval,
+        );
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+
+        let now = SystemTime::now();
+        *last = Some(now);
+        if let Some(path) = &self.settings.state {
+            let millis = now
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = fs::write(path, millis.to_string());
+        }
+    }
+}
+
+/// How long to wait before a request when the
+/// previous one was made at `last`.
+fn delay(
+    last: Option<SystemTime>,
+    now: SystemTime,
+    min_interval: Duration,
+) -> Duration {
+    let Some(last) = last else {
+        return Duration::ZERO;
+    };
+    // A `last` in the future means the clock went
+    // back; waiting the whole interval is safest.
+    let elapsed =
+        now.duration_since(last).unwrap_or_default();
+    min_interval.saturating_sub(elapsed)
+}
+
+/// How long to wait before retry number
+/// `attempt` (counting from 0).
+fn backoff(
+    settings: &Settings,
+    attempt: u32,
+    retry_after: Option<Duration>,
+) -> Duration {
+    let exponential = settings
+        .backoff
+        .saturating_mul(2u32.saturating_pow(attempt));
+    exponential
+        .max(retry_after.unwrap_or_default())
+        .min(settings.max_wait)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_user_agent() {
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            format!(
+                "fetch-input/{version} (me@example.com)"
+            ),
+            user_agent(Some("me@example.com"))
+        );
+        assert_eq!(
+            format!("fetch-input/{version}"),
+            user_agent(None)
+        );
+    }
+
+    #[rstest]
+    #[case(None, 0, 0)]
+    #[case(Some(0), 1, 2)]
+    #[case(Some(0), 3, 0)]
+    #[case(Some(0), 10, 0)]
+    #[case(Some(5), 0, 3)]
+    fn test_delay(
+        #[case] last: Option<u64>,
+        #[case] now: u64,
+        #[case] expected: u64,
+    ) {
+        let at =
+            |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(
+            Duration::from_secs(expected),
+            delay(
+                last.map(at),
+                at(now),
+                Duration::from_secs(3)
+            )
+        );
+    }
+
+    #[rstest]
+    #[case(0, None, 5)]
+    #[case(1, None, 10)]
+    #[case(2, None, 20)]
+    #[case(0, Some(30), 30)]
+    #[case(1, Some(1), 10)]
+    #[case(5, None, 120)]
+    #[case(0, Some(3600), 120)]
+    fn test_backoff(
+        #[case] attempt: u32,
+        #[case] retry_after: Option<u64>,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(
+            Duration::from_secs(expected),
+            backoff(
+                &Settings::default(),
+                attempt,
+                retry_after.map(Duration::from_secs)
+            )
+        );
+    }
+
+    #[test]
+    fn test_throttle_state_is_shared() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = Settings {
+            state: Some(dir.path().join("last-request")),
+            min_interval: Duration::from_millis(200),
+            ..Settings::default()
+        };
+
+        Http::new(settings.clone()).throttle();
+        let started = std::time::Instant::now();
+        Http::new(settings).throttle();
+        assert!(
+            started.elapsed() >= Duration::from_millis(150)
+        );
+    }
+}
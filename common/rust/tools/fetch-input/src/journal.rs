@@ -0,0 +1,301 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::Part;
+use miette::NamedSource;
+use serde::{Deserialize, Serialize};
+
+use crate::{client::write_atomically, error::FetchError};
+
+/// When each day was started and solved, kept in
+/// the inputs directory as `journal.toml`:
+///
+/// ```toml
+/// [2024.day-05]
+/// fetched = 1733374805
+/// part1 = 1733375412
+/// part2 = 1733376090
+/// ```
+///
+/// Only the first time of each is kept, so
+/// fetching or verifying again doesn't move them.
+#[derive(
+    Debug, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Journal {
+    years: BTreeMap<String, BTreeMap<String, Entry>>,
+}
+
+/// One day of the journal, in seconds since the
+/// Unix epoch.
+#[derive(
+    Debug, Default, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    /// When the input was first downloaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched: Option<u64>,
+    /// When part one's answer was first accepted
+    /// or verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<u64>,
+}
+
+impl Entry {
+    pub fn solved(&self, part: Part) -> Option<u64> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Path of the journal inside an inputs
+/// directory.
+pub fn path(dir: &Path) -> PathBuf {
+    dir.join("journal.toml")
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Sets `slot` to `at` unless it's already set.
+fn first(slot: &mut Option<u64>, at: u64) -> bool {
+    if slot.is_some() {
+        return false;
+    }
+    *slot = Some(at);
+    true
+}
+
+fn day_key(day: u8) -> String {
+    format!("day-{day:02}")
+}
+
+impl Journal {
+    /// Reads the journal at `path`, which is fine
+    /// to not exist yet.
+    pub fn load(
+        path: &Path,
+    ) -> Result<Journal, FetchError> {
+        let src = match std::fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e)
+                if e.kind() == io::ErrorKind::NotFound =>
+            {
+                return Ok(Journal::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Journal::parse(&path.display().to_string(), src)
+    }
+
+    fn parse(
+        name: &str,
+        src: String,
+    ) -> Result<Journal, FetchError> {
+        toml::from_str(&src).map_err(|e| {
+            FetchError::InvalidJournal {
+                span: e.span().map(Into::into),
+                message: e.message().to_string(),
+                src: NamedSource::new(name, src),
+            }
+        })
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> Result<(), FetchError> {
+        let toml = toml::to_string(self)
+            .expect("journal serializes to TOML");
+        write_atomically(path, &toml).map_err(|source| {
+            FetchError::Write {
+                path: path.to_path_buf(),
+                source,
+            }
+        })
+    }
+
+    pub fn get(
+        &self,
+        year: u16,
+        day: u8,
+    ) -> Option<&Entry> {
+        self.years
+            .get(&year.to_string())?
+            .get(&day_key(day))
+    }
+
+    fn entry(&mut self, year: u16, day: u8) -> &mut Entry {
+        self.years
+            .entry(year.to_string())
+            .or_default()
+            .entry(day_key(day))
+            .or_default()
+    }
+
+    /// Notes that the day's input was downloaded
+    /// at `at`, unless it was before. Returns
+    /// whether anything changed.
+    pub fn fetched(
+        &mut self,
+        year: u16,
+        day: u8,
+        at: u64,
+    ) -> bool {
+        first(&mut self.entry(year, day).fetched, at)
+    }
+
+    /// Notes that a part was solved at `at`,
+    /// unless it was before. Returns whether
+    /// anything changed.
+    pub fn solved(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        at: u64,
+    ) -> bool {
+        let entry = self.entry(year, day);
+        match part {
+            Part::One => first(&mut entry.part1, at),
+            Part::Two => first(&mut entry.part2, at),
+        }
+    }
+
+    /// Every day in the journal, in order.
+    pub fn entries(
+        &self,
+    ) -> impl Iterator<Item = (u16, u8, &Entry)> {
+        self.years.iter().flat_map(|(year, days)| {
+            days.iter().filter_map(move |(day, entry)| {
+                let year = year.parse().ok()?;
+                let day = day
+                    .strip_prefix("day-")?
+                    .parse()
+                    .ok()?;
+                Some((year, day, entry))
+            })
+        })
+    }
+}
+
+/// Notes in the journal in `inputs` that a day's
+/// input was just downloaded.
+pub fn record_fetched(
+    inputs: &Path,
+    year: u16,
+    day: u8,
+) -> Result<(), FetchError> {
+    let path = path(inputs);
+    let mut journal = Journal::load(&path)?;
+    if journal.fetched(year, day, now()) {
+        journal.save(&path)?;
+    }
+    Ok(())
+}
+
+/// Notes in the journal in `inputs` that a part
+/// was just solved.
+pub fn record_solved(
+    inputs: &Path,
+    year: u16,
+    day: u8,
+    part: Part,
+) -> Result<(), FetchError> {
+    let path = path(inputs);
+    let mut journal = Journal::load(&path)?;
+    if journal.solved(year, day, part, now()) {
+        journal.save(&path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_first_times_are_kept() {
+        let mut journal = Journal::default();
+        assert!(journal.fetched(2024, 5, 100));
+        assert!(!journal.fetched(2024, 5, 200));
+        assert!(journal.solved(2024, 5, Part::One, 300));
+        assert!(!journal.solved(2024, 5, Part::One, 400));
+        assert_eq!(
+            Some(&Entry {
+                fetched: Some(100),
+                part1: Some(300),
+                part2: None,
+            }),
+            journal.get(2024, 5)
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = path(dir.path());
+        assert_eq!(
+            Journal::default(),
+            Journal::load(&path)?
+        );
+
+        let mut journal = Journal::default();
+        journal.fetched(2024, 5, 100);
+        journal.solved(2024, 5, Part::Two, 300);
+        journal.solved(2023, 1, Part::One, 50);
+        journal.save(&path)?;
+
+        assert_eq!(
+            "[2023.day-01]\n\
+             part1 = 50\n\
+             \n\
+             [2024.day-05]\n\
+             fetched = 100\n\
+             part2 = 300\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+        let loaded = Journal::load(&path)?;
+        assert_eq!(journal, loaded);
+        assert_eq!(
+            vec![(2023, 1), (2024, 5)],
+            loaded
+                .entries()
+                .map(|(year, day, _)| (year, day))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_points_at_mistakes() {
+        let err = Journal::parse(
+            "journal.toml",
+            "[2024.day-05]\nfetched = \"yesterday\"\n"
+                .to_string(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            FetchError::InvalidJournal {
+                span: Some(_),
+                ..
+            }
+        ));
+    }
+}
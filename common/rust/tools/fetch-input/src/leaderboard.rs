@@ -0,0 +1,350 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use aoc_core::Part;
+use serde::Deserialize;
+
+use crate::{
+    Client, client::write_atomically, error::FetchError,
+};
+
+/// The site asks for private leaderboards to be
+/// downloaded no more often than this.
+pub const MIN_REFRESH: Duration =
+    Duration::from_secs(15 * 60);
+
+/// A private leaderboard as the site's JSON API
+/// has it. Fields the viewer doesn't use are
+/// left out.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u32,
+    pub stars: u32,
+    /// Stars by day and then by part, both as
+    /// strings.
+    #[serde(default)]
+    pub completion_day_level:
+        BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Star {
+    /// When it was earned, in Unix seconds.
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    /// The member's name, or how the site shows
+    /// anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                format!("(anonymous user #{})", self.id)
+            }
+        }
+    }
+
+    /// When the member earned a day's star.
+    pub fn star(&self, day: u8, part: Part) -> Option<u64> {
+        let star = self
+            .completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())?;
+        Some(star.get_star_ts)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(
+        url: &str,
+        json: &str,
+    ) -> Result<Leaderboard, FetchError> {
+        serde_json::from_str(json).map_err(|e| {
+            FetchError::InvalidLeaderboard {
+                url: url.to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    /// Members by score, best first; ties go to
+    /// whoever has more stars, then by name.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<_> =
+            self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(
+                    a.display_name().cmp(&b.display_name()),
+                )
+        });
+        members
+    }
+
+    /// Days anyone has a star for.
+    pub fn days(&self) -> BTreeSet<u8> {
+        self.members
+            .values()
+            .flat_map(|member| {
+                member.completion_day_level.keys()
+            })
+            .filter_map(|day| day.parse().ok())
+            .collect()
+    }
+
+    /// Points each member got on a day, by id.
+    ///
+    /// The first of N members to get a star gets
+    /// N points for it, the second N - 1 and so
+    /// on, which is how the site adds up
+    /// `local_score`.
+    pub fn day_points(
+        &self,
+        day: u8,
+    ) -> BTreeMap<u64, u32> {
+        let size = self.members.len() as u32;
+        let mut points: BTreeMap<u64, u32> = self
+            .members
+            .values()
+            .map(|member| (member.id, 0))
+            .collect();
+        for part in [Part::One, Part::Two] {
+            let mut stars: Vec<_> = self
+                .members
+                .values()
+                .filter_map(|member| {
+                    Some((
+                        member.star(day, part)?,
+                        member.id,
+                    ))
+                })
+                .collect();
+            stars.sort();
+            for (rank, (_, id)) in
+                stars.into_iter().enumerate()
+            {
+                *points.entry(id).or_default() +=
+                    size - rank as u32;
+            }
+        }
+        points
+    }
+}
+
+/// A leaderboard along with the copy it replaced,
+/// so changes since then can be shown.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub leaderboard: Leaderboard,
+    pub previous: Option<Leaderboard>,
+    pub fetched_at: SystemTime,
+}
+
+/// `aoc/leaderboards` in the user's cache
+/// directory.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("aoc").join("leaderboards"))
+}
+
+/// Where a leaderboard is cached, e.g.
+/// `2024-123456.json`.
+pub fn cache_path(
+    dir: &Path,
+    year: u16,
+    id: u64,
+) -> PathBuf {
+    dir.join(format!("{year}-{id}.json"))
+}
+
+fn read_cached(
+    path: &Path,
+) -> Result<Option<(String, SystemTime)>, FetchError> {
+    let read = || {
+        let json = fs::read_to_string(path)?;
+        let modified = fs::metadata(path)?.modified()?;
+        Ok::<_, io::Error>((json, modified))
+    };
+    match read() {
+        Ok(cached) => Ok(Some(cached)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Returns the leaderboard cached in `cache` if
+/// it was downloaded less than [`MIN_REFRESH`]
+/// before `now`, and downloads it again
+/// otherwise, keeping the old copy as the
+/// previous one.
+pub fn load(
+    client: &Client,
+    year: u16,
+    id: u64,
+    cache: &Path,
+    now: SystemTime,
+) -> Result<Snapshot, FetchError> {
+    let url = client.leaderboard_url(year, id);
+    let path = cache_path(cache, year, id);
+    let previous_path =
+        path.with_extension("previous.json");
+    let previous = read_cached(&previous_path)?
+        .map(|(json, _)| Leaderboard::parse(&url, &json))
+        .transpose()?;
+
+    let cached = read_cached(&path)?;
+    if let Some((json, fetched_at)) = &cached
+        && now
+            .duration_since(*fetched_at)
+            .is_ok_and(|age| age < MIN_REFRESH)
+    {
+        return Ok(Snapshot {
+            leaderboard: Leaderboard::parse(&url, json)?,
+            previous,
+            fetched_at: *fetched_at,
+        });
+    }
+
+    let json = client.leaderboard_json(year, id)?;
+    let leaderboard = Leaderboard::parse(&url, &json)?;
+    let write = |path: &Path, json: &str| {
+        write_atomically(path, json).map_err(|source| {
+            FetchError::Write {
+                path: path.to_path_buf(),
+                source,
+            }
+        })
+    };
+    let previous = match cached {
+        Some((old, _)) => {
+            write(&previous_path, &old)?;
+            Some(Leaderboard::parse(&url, &old)?)
+        }
+        None => previous,
+    };
+    write(&path, &json)?;
+    Ok(Snapshot {
+        leaderboard,
+        previous,
+        fetched_at: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str =
+        include_str!("../tests/fixtures/leaderboard.json");
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard::parse("leaderboard.json", JSON)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = leaderboard();
+        assert_eq!("2024", leaderboard.event);
+        assert_eq!(4, leaderboard.members.len());
+        let alice = &leaderboard.members["101"];
+        assert_eq!("Alice", alice.display_name());
+        assert_eq!(
+            Some(1733029800),
+            alice.star(1, Part::Two)
+        );
+        assert_eq!(None, alice.star(3, Part::One));
+        assert_eq!(
+            "(anonymous user #303)",
+            leaderboard.members["303"].display_name()
+        );
+    }
+
+    #[test]
+    fn test_parse_html() {
+        let err = Leaderboard::parse(
+            "leaderboard.json",
+            "<!DOCTYPE html>",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            FetchError::InvalidLeaderboard { .. }
+        ));
+    }
+
+    #[test]
+    fn test_standings() {
+        let names: Vec<_> = leaderboard()
+            .standings()
+            .into_iter()
+            .map(Member::display_name)
+            .collect();
+        assert_eq!(
+            vec![
+                "Alice",
+                "Bob",
+                "(anonymous user #303)",
+                "Dave"
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_days() {
+        assert_eq!(
+            BTreeSet::from([1, 2]),
+            leaderboard().days()
+        );
+    }
+
+    #[test]
+    fn test_day_points_add_up_to_local_score() {
+        let leaderboard = leaderboard();
+        let mut totals: BTreeMap<u64, u32> =
+            BTreeMap::new();
+        for day in leaderboard.days() {
+            for (id, points) in leaderboard.day_points(day)
+            {
+                *totals.entry(id).or_default() += points;
+            }
+        }
+        for member in leaderboard.members.values() {
+            assert_eq!(
+                member.local_score,
+                totals[&member.id],
+                "{}",
+                member.display_name()
+            );
+        }
+        assert_eq!(
+            BTreeMap::from([
+                (101, 7),
+                (202, 4),
+                (303, 0),
+                (404, 0)
+            ]),
+            leaderboard.day_points(2)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Downloads puzzle inputs from adventofcode.com,
+//! or anything answering the same URLs, and
+//! submits answers to it.
+
+pub mod bulk;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod examples;
+pub mod history;
+pub mod http;
+pub mod journal;
+pub mod leaderboard;
+pub mod puzzle;
+pub mod response;
+pub mod session;
+pub mod submit;
+
+pub use client::Client;
+pub use error::FetchError;
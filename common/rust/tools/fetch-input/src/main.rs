@@ -0,0 +1,117 @@
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use aoc_core::input;
+use fetch_input::{
+    bulk,
+    cli::{
+        Site, check_days, days_in, parse_day, parse_days,
+    },
+    journal,
+};
+use miette::miette;
+
+#[derive(clap::Parser, Debug)]
+#[clap(version)]
+#[clap(group(
+    clap::ArgGroup::new("which")
+        .required(true)
+        .args(["day", "all", "days"])
+))]
+struct Args {
+    /// Years may pass, but the pursuit of skill
+    /// mastery continues.
+    #[clap(short, long)]
+    year: u16,
+    /// Either "5", "05" or "day-05".
+    #[clap(short, long, value_parser = parse_day)]
+    day: Option<u8>,
+    /// Fetches every day of the year that isn't
+    /// cached yet: 25 before 2025, 12 since.
+    #[clap(long)]
+    all: bool,
+    /// Fetches a range of days formatted as
+    /// "1..=10" that aren't cached yet.
+    #[clap(long, value_parser = parse_days)]
+    days: Option<RangeInclusive<u8>>,
+    /// Where inputs are cached, as
+    /// `{year}/day-{day}.txt`. Defaults to
+    /// `$AOC_INPUTS` or `inputs/` at the
+    /// repository root.
+    #[clap(long, value_name = "DIR")]
+    inputs: Option<PathBuf>,
+    /// Downloads the input again even if it's
+    /// already cached.
+    #[clap(short, long)]
+    force: bool,
+    #[clap(flatten)]
+    site: Site,
+}
+
+fn main() -> miette::Result<()> {
+    use clap::Parser;
+
+    let args = Args::parse();
+
+    let inputs =
+        args.inputs.unwrap_or_else(input::default_dir);
+    if let Some(days) =
+        args.days.clone().or(args.day.map(|day| day..=day))
+    {
+        check_days(args.year, &days)
+            .map_err(|e| miette!("{e}"))?;
+    }
+    let Some(day) = args.day else {
+        let days =
+            args.days.unwrap_or_else(|| days_in(args.year));
+        let (client, origin) = args.site.connect()?;
+        println!(
+            "Using session {} from {origin}",
+            client.session()
+        );
+        let statuses = bulk::fetch_days(
+            &client,
+            &inputs,
+            args.year,
+            days,
+            args.force,
+            |day, status| {
+                println!("day-{day:02}  {status}")
+            },
+        );
+        println!("{}", bulk::summary(&statuses));
+        let failed = statuses.iter().any(|(_, status)| {
+            matches!(status, bulk::Status::Failed { .. })
+        });
+        return if failed {
+            Err(miette!(
+                "Some inputs couldn't be fetched"
+            ))
+        } else {
+            Ok(())
+        };
+    };
+
+    let file_path = input::path(&inputs, args.year, day);
+    if file_path.exists() && !args.force {
+        println!(
+            "Using cached {} (pass --force to download it again)",
+            file_path.display()
+        );
+        return Ok(());
+    }
+
+    let (client, origin) = args.site.connect()?;
+    println!(
+        "Using session {} from {origin}",
+        client.session()
+    );
+    println!(
+        "Getting input from `{}`",
+        client.input_url(args.year, day)
+    );
+    client.download_input(args.year, day, &file_path)?;
+    println!("Wrote {}", file_path.display());
+    journal::record_fetched(&inputs, args.year, day)?;
+
+    Ok(())
+}
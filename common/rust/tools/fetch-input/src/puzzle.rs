@@ -0,0 +1,400 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use ego_tree::NodeRef;
+use scraper::{Html, Node, Selector};
+
+use crate::{
+    Client, FetchError, client::write_atomically, examples,
+};
+
+/// One `<article class="day-desc">` of a puzzle
+/// page, converted to Markdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Article {
+    /// e.g. "Day 5: Print Queue" or "Part Two".
+    pub title: String,
+    pub body: String,
+}
+
+/// Every part of the puzzle description that is
+/// unlocked on `html`, a day's page. Relative
+/// links are made absolute against `base_url`.
+pub fn articles(
+    html: &str,
+    base_url: &str,
+) -> Vec<Article> {
+    let html = Html::parse_document(html);
+    let articles = Selector::parse("article.day-desc")
+        .expect("valid selector");
+    let heading =
+        Selector::parse("h2").expect("valid selector");
+
+    html.select(&articles)
+        .map(|article| {
+            let title = article
+                .select(&heading)
+                .next()
+                .map(|h2| h2.text().collect::<String>())
+                .unwrap_or_default();
+            let mut body = String::new();
+            blocks(*article, base_url, &mut body);
+            Article {
+                title: title
+                    .trim()
+                    .trim_matches('-')
+                    .trim()
+                    .to_string(),
+                body: body.trim_end().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Renders a day's `README.md`, part one as the
+/// title and every later part as a section.
+pub fn readme(url: &str, articles: &[Article]) -> String {
+    let mut readme = String::new();
+    for (i, article) in articles.iter().enumerate() {
+        if i == 0 {
+            readme.push_str(&format!(
+                "# {}\n\n<{url}>\n\n{}\n",
+                article.title, article.body
+            ));
+        } else {
+            readme.push_str(&section(article));
+        }
+    }
+    readme
+}
+
+/// Brings an existing `README.md` up to date by
+/// appending the parts it doesn't have yet,
+/// leaving anything written by hand alone.
+/// Returns `None` when there is nothing to add.
+pub fn update_readme(
+    existing: Option<&str>,
+    url: &str,
+    articles: &[Article],
+) -> Option<String> {
+    let Some(existing) = existing else {
+        return (!articles.is_empty())
+            .then(|| readme(url, articles));
+    };
+
+    let missing: String = articles
+        .iter()
+        .skip(1)
+        .filter(|article| {
+            !existing.contains(&format!(
+                "## {}\n",
+                article.title
+            ))
+        })
+        .map(section)
+        .collect();
+    (!missing.is_empty())
+        .then(|| format!("{existing}{missing}"))
+}
+
+/// Downloads a day's description into
+/// `dir/README.md`, adding part two once it's
+/// unlocked, and saves its examples in
+/// `dir/examples`. Returns the files written.
+pub fn download(
+    client: &Client,
+    year: u16,
+    day: u8,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, FetchError> {
+    let url = client.puzzle_url(year, day);
+    let html = client.puzzle_page(year, day)?;
+
+    let articles = articles(&html, client.base_url());
+    if articles.is_empty() {
+        return Err(FetchError::NoDescription { url });
+    }
+
+    let mut written = vec![];
+    let path = dir.join("README.md");
+    let existing = match fs::read_to_string(&path) {
+        Ok(readme) => Some(readme),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(readme) =
+        update_readme(existing.as_deref(), &url, &articles)
+    {
+        write_atomically(&path, &readme).map_err(
+            |source| FetchError::Write {
+                path: path.clone(),
+                source,
+            },
+        )?;
+        written.push(path);
+    }
+
+    let examples_dir = dir.join("examples");
+    written.extend(
+        examples::save(
+            &examples_dir,
+            &examples::extract(&html),
+        )
+        .map_err(|source| FetchError::Write {
+            path: examples_dir.clone(),
+            source,
+        })?,
+    );
+    Ok(written)
+}
+
+fn section(article: &Article) -> String {
+    format!(
+        "\n## {}\n\n{}\n",
+        article.title, article.body
+    )
+}
+
+fn blocks(
+    node: NodeRef<'_, Node>,
+    base_url: &str,
+    out: &mut String,
+) {
+    for child in node.children() {
+        match child.value() {
+            Node::Element(element) => {
+                match element.name() {
+                    // Already used as the title.
+                    "h2" => {}
+                    "p" => {
+                        out.push_str(
+                            inline(child, base_url).trim(),
+                        );
+                        out.push_str("\n\n");
+                    }
+                    "pre" => {
+                        let code = text(child);
+                        out.push_str("```\n");
+                        out.push_str(&code);
+                        if !code.ends_with('\n') {
+                            out.push('\n');
+                        }
+                        out.push_str("```\n\n");
+                    }
+                    "ul" | "ol" => {
+                        for item in child.children() {
+                            if matches!(item.value(), Node::Element(e) if e.name() == "li")
+                            {
+                                out.push_str("- ");
+                                out.push_str(
+                                    inline(item, base_url)
+                                        .trim(),
+                                );
+                                out.push('\n');
+                            }
+                        }
+                        out.push('\n');
+                    }
+                    _ => blocks(child, base_url, out),
+                }
+            }
+            Node::Text(t) if !t.trim().is_empty() => {
+                out.push_str(t.trim());
+                out.push_str("\n\n");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn inline(
+    node: NodeRef<'_, Node>,
+    base_url: &str,
+) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        match child.value() {
+            Node::Text(t) => {
+                out.push_str(&t.replace('\n', " "))
+            }
+            Node::Element(element) => {
+                match element.name() {
+                    "code" => {
+                        let code = code_span(&text(child));
+                        if has_emphasis(child) {
+                            out.push_str(&format!(
+                                "**{code}**"
+                            ));
+                        } else {
+                            out.push_str(&code);
+                        }
+                    }
+                    "em" => {
+                        out.push_str(&format!(
+                            "**{}**",
+                            inline(child, base_url)
+                        ));
+                    }
+                    "a" => {
+                        let href = element
+                            .attr("href")
+                            .unwrap_or("");
+                        let href = if href.starts_with('/')
+                        {
+                            format!("{base_url}{href}")
+                        } else {
+                            href.to_string()
+                        };
+                        out.push_str(&format!(
+                            "[{}]({href})",
+                            inline(child, base_url)
+                        ));
+                    }
+                    "br" => out.push_str("  \n"),
+                    _ => out
+                        .push_str(&inline(child, base_url)),
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn text(node: NodeRef<'_, Node>) -> String {
+    node.descendants()
+        .filter_map(|n| match n.value() {
+            Node::Text(t) => Some(&**t),
+            _ => None,
+        })
+        .collect()
+}
+
+fn has_emphasis(node: NodeRef<'_, Node>) -> bool {
+    node.descendants().any(
+        |n| matches!(n.value(), Node::Element(e) if e.name() == "em"),
+    )
+}
+
+fn code_span(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {code} ``")
+    } else {
+        format!("`{code}`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://adventofcode.com/2024/day/1";
+
+    const PART_ONE: &str = r#"<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> was visiting locations that are <a href="/2015">historically significant</a>.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul>
+<li>The distance between them is <code><em>2</em></code>.</li>
+<li>Then <code>1</code>.</li>
+</ul>
+<p><span title="Hover text">Your</span> actual lists contain <em>many</em> location IDs.</p>
+</article>"#;
+
+    const PART_TWO: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>This time, the answer is <code><em>31</em></code>.</p></article>"#;
+
+    fn page(articles: &[&str]) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main>{}\
+             <p>Your puzzle answer was <code>11</code>.</p>\
+             </main></body></html>",
+            articles.join("")
+        )
+    }
+
+    #[test]
+    fn test_articles() {
+        let articles = articles(
+            &page(&[PART_ONE, PART_TWO]),
+            "https://adventofcode.com",
+        );
+        assert_eq!(2, articles.len());
+        assert_eq!(
+            "Day 1: Historian Hysteria",
+            articles[0].title
+        );
+        assert_eq!(
+            "The **Chief Historian** was visiting locations that are \
+             [historically significant](https://adventofcode.com/2015).\n\
+             \n\
+             For example:\n\
+             \n\
+             ```\n\
+             3   4\n\
+             4   3\n\
+             ```\n\
+             \n\
+             - The distance between them is **`2`**.\n\
+             - Then `1`.\n\
+             \n\
+             Your actual lists contain **many** location IDs.",
+            articles[0].body
+        );
+        assert_eq!(
+            Article {
+                title: "Part Two".to_string(),
+                body: "This time, the answer is **`31`**."
+                    .to_string(),
+            },
+            articles[1]
+        );
+    }
+
+    #[test]
+    fn test_readme() {
+        let articles =
+            articles(&page(&[PART_ONE, PART_TWO]), "");
+        let readme = readme(URL, &articles);
+        assert!(readme.starts_with(
+            "# Day 1: Historian Hysteria\n\n<https://adventofcode.com/2024/day/1>\n\n"
+        ));
+        assert!(readme.ends_with(
+            "\n## Part Two\n\nThis time, the answer is **`31`**.\n"
+        ));
+    }
+
+    #[test]
+    fn test_update_readme_appends_part_two() {
+        let part_one = articles(&page(&[PART_ONE]), "");
+        let both =
+            articles(&page(&[PART_ONE, PART_TWO]), "");
+
+        let readme = update_readme(None, URL, &part_one)
+            .expect("a new README");
+        assert!(
+            update_readme(Some(&readme), URL, &part_one)
+                .is_none()
+        );
+
+        let notes =
+            format!("{readme}\nSome notes of my own.\n");
+        let updated =
+            update_readme(Some(&notes), URL, &both)
+                .expect("part two to be appended");
+        assert_eq!(
+            format!(
+                "{notes}\n## Part Two\n\nThis time, the answer is **`31`**.\n"
+            ),
+            updated
+        );
+        assert!(
+            update_readme(Some(&updated), URL, &both)
+                .is_none()
+        );
+    }
+}
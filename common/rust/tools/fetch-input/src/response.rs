@@ -0,0 +1,322 @@
+use crate::{
+    error::FetchError,
+    submit::{self, Verdict},
+};
+
+/// What the site says before a puzzle unlocks.
+const NOT_UNLOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks";
+
+/// What the site says when the cookie is missing,
+/// malformed or expired.
+const LOG_IN: &str = "Please log in";
+
+/// Checks a response to an input request, turning
+/// the site's error pages into typed errors so
+/// they never end up saved as an input.
+pub fn check_input(
+    url: &str,
+    year: u16,
+    day: u8,
+    status: u16,
+    body: String,
+) -> Result<String, FetchError> {
+    if body.contains(LOG_IN) {
+        return Err(FetchError::ExpiredSession);
+    }
+    let body = check_status(url, year, day, status, body)?;
+    if body.trim().is_empty() || looks_like_html(&body) {
+        return Err(FetchError::UnexpectedResponse {
+            url: url.to_string(),
+            status,
+            excerpt: excerpt(&body),
+        });
+    }
+    Ok(body)
+}
+
+/// Checks a response to an HTML page request,
+/// e.g. a puzzle description.
+pub fn check_page(
+    url: &str,
+    year: u16,
+    day: u8,
+    status: u16,
+    body: String,
+) -> Result<String, FetchError> {
+    check_status(url, year, day, status, body)
+}
+
+/// Checks a response to an answer submission and
+/// reads the verdict off it.
+pub fn check_answer(
+    url: &str,
+    year: u16,
+    day: u8,
+    status: u16,
+    body: String,
+) -> Result<Verdict, FetchError> {
+    let body = check_status(url, year, day, status, body)?;
+    submit::parse(&body).ok_or_else(|| {
+        FetchError::UnknownVerdict {
+            url: url.to_string(),
+            excerpt: excerpt(&body),
+        }
+    })
+}
+
+/// Checks a response to a private leaderboard
+/// request. It has no day, so unlike a day's
+/// pages it can't be locked, and a 404 means the
+/// leaderboard `id` doesn't exist.
+pub fn check_leaderboard(
+    url: &str,
+    year: u16,
+    id: u64,
+    status: u16,
+    body: String,
+) -> Result<String, FetchError> {
+    match status {
+        404 => Err(FetchError::NoLeaderboard { year, id }),
+        status => check_code(url, status, body),
+    }
+}
+
+fn check_status(
+    url: &str,
+    year: u16,
+    day: u8,
+    status: u16,
+    body: String,
+) -> Result<String, FetchError> {
+    if body.contains(NOT_UNLOCKED) {
+        return Err(FetchError::NotUnlocked { year, day });
+    }
+    check_code(url, status, body)
+}
+
+fn check_code(
+    url: &str,
+    status: u16,
+    body: String,
+) -> Result<String, FetchError> {
+    match status {
+        200 => Ok(body),
+        400 | 401 | 403 => Err(FetchError::ExpiredSession),
+        429 => Err(FetchError::RateLimited {
+            url: url.to_string(),
+        }),
+        404 => Err(FetchError::NotFound {
+            url: url.to_string(),
+        }),
+        status => Err(FetchError::UnexpectedResponse {
+            url: url.to_string(),
+            status,
+            excerpt: excerpt(&body),
+        }),
+    }
+}
+
+fn looks_like_html(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype")
+        || start.starts_with("<html")
+}
+
+pub(crate) fn excerpt(body: &str) -> String {
+    let body = body.trim();
+    if body.is_empty() {
+        return "The response was empty.".to_string();
+    }
+    let mut excerpt: String =
+        body.chars().take(200).collect();
+    if excerpt.len() < body.len() {
+        excerpt.push('…');
+    }
+    format!("The response started with:\n{excerpt}")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const URL: &str =
+        "https://adventofcode.com/2024/day/5/input";
+
+    fn check(
+        status: u16,
+        body: &str,
+    ) -> Result<String, FetchError> {
+        check_input(URL, 2024, 5, status, body.to_string())
+    }
+
+    #[test]
+    fn test_input() -> miette::Result<()> {
+        assert_eq!("47|53\n", check(200, "47|53\n")?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks! \
+         The calendar countdown is synchronized with the server time; \
+         the link will be enabled on the calendar the instant this \
+         puzzle becomes available.\n"
+    )]
+    #[case(
+        200,
+        "Please don't repeatedly request this endpoint before it unlocks!"
+    )]
+    fn test_not_unlocked(
+        #[case] status: u16,
+        #[case] body: &str,
+    ) {
+        assert!(matches!(
+            check(status, body),
+            Err(FetchError::NotUnlocked {
+                year: 2024,
+                day: 5
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case(
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+    )]
+    #[case(403, "")]
+    #[case(200, "Please log in to get your puzzle input.")]
+    fn test_expired_session(
+        #[case] status: u16,
+        #[case] body: &str,
+    ) {
+        assert!(matches!(
+            check(status, body),
+            Err(FetchError::ExpiredSession)
+        ));
+    }
+
+    #[test]
+    fn test_not_found() {
+        assert!(matches!(
+            check(404, "404 Not Found\n"),
+            Err(FetchError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        assert!(matches!(
+            check(429, "Too Many Requests"),
+            Err(FetchError::RateLimited { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(200, "")]
+    #[case(200, "<!DOCTYPE html>\n<html lang=\"en-us\">")]
+    #[case(500, "500 Internal Server Error\n")]
+    #[case(502, "Bad Gateway")]
+    fn test_unexpected(
+        #[case] status: u16,
+        #[case] body: &str,
+    ) {
+        assert!(matches!(
+            check(status, body),
+            Err(FetchError::UnexpectedResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_page() -> miette::Result<()> {
+        let html = "<!DOCTYPE html>\n<html lang=\"en-us\">";
+        assert_eq!(
+            html,
+            check_page(
+                URL,
+                2024,
+                5,
+                200,
+                html.to_string()
+            )?
+        );
+        assert!(matches!(
+            check_page(URL, 2024, 5, 404, "".to_string()),
+            Err(FetchError::NotFound { .. })
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(404, "404 Not Found\n")]
+    #[case(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks!"
+    )]
+    fn test_no_leaderboard(
+        #[case] status: u16,
+        #[case] body: &str,
+    ) {
+        let err = check_leaderboard(
+            URL,
+            2024,
+            101,
+            status,
+            body.to_string(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            FetchError::NoLeaderboard {
+                year: 2024,
+                id: 101
+            }
+        ));
+        assert_eq!(
+            "There is no private leaderboard 101 in 2024",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_leaderboard() -> miette::Result<()> {
+        assert_eq!(
+            "{}",
+            check_leaderboard(
+                URL,
+                2024,
+                101,
+                200,
+                "{}".to_string()
+            )?
+        );
+        assert!(matches!(
+            check_leaderboard(
+                URL,
+                2024,
+                101,
+                403,
+                "".to_string()
+            ),
+            Err(FetchError::ExpiredSession)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_excerpt_is_truncated() {
+        let long = "x".repeat(500);
+        let err = check(503, &long).unwrap_err();
+        let FetchError::UnexpectedResponse {
+            excerpt, ..
+        } = err
+        else {
+            panic!("expected an unexpected response");
+        };
+        assert!(excerpt.ends_with('…'));
+        assert!(excerpt.len() < 300);
+    }
+}
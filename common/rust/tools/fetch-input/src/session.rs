@@ -0,0 +1,428 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{config::Config, error::FetchError};
+
+/// Environment variables checked for a token, in
+/// order. `SESSION` is what older setups used.
+pub const SESSION_ENV: [&str; 2] =
+    ["AOC_SESSION", "SESSION"];
+
+/// Advent of Code session cookie.
+///
+/// Both `Debug` and `Display` redact it, so it's
+/// safe to log; [`Session::cookie`] is the only
+/// way to get at the token itself.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub struct Session(String);
+
+impl Session {
+    pub fn new(token: impl Into<String>) -> Self {
+        Session(token.into().trim().to_string())
+    }
+
+    /// Value of the `Cookie` header.
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.0)
+    }
+}
+
+impl From<String> for Session {
+    fn from(token: String) -> Self {
+        Session::new(token)
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        // The last few characters are enough to tell
+        // accounts apart without leaking anything
+        // useful.
+        let chars = self.0.chars().count();
+        if chars < 16 {
+            return write!(f, "********");
+        }
+        let tail: String =
+            self.0.chars().skip(chars - 4).collect();
+        write!(f, "********{tail}")
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "Session({self})")
+    }
+}
+
+/// Where a session token was found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Env(&'static str),
+    DotEnv(PathBuf),
+    Profile { name: String, config: PathBuf },
+}
+
+impl fmt::Display for Origin {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Origin::Env(var) => write!(f, "${var}"),
+            Origin::DotEnv(path) => {
+                write!(f, "{}", path.display())
+            }
+            Origin::Profile { name, config } => write!(
+                f,
+                "the `{name}` profile in {}",
+                config.display()
+            ),
+        }
+    }
+}
+
+/// Everything a session token may come from.
+pub struct Lookup<'a> {
+    /// Profile asked for explicitly, which skips
+    /// the environment and `.env`.
+    pub profile: Option<&'a str>,
+    pub env: &'a dyn Fn(&str) -> Option<String>,
+    pub dotenv: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+}
+
+impl<'a> Lookup<'a> {
+    /// Looks in the process environment, the
+    /// nearest `.env` file and the user's config
+    /// file.
+    pub fn from_env(profile: Option<&'a str>) -> Self {
+        Lookup {
+            profile,
+            env: &|var| std::env::var(var).ok(),
+            dotenv: find_dotenv(),
+            config: crate::config::default_path(),
+        }
+    }
+
+    /// Finds a token, trying an explicit profile,
+    /// then the environment, then `.env`, then
+    /// the config file's default profile. The
+    /// config file is only read when one of its
+    /// profiles is needed.
+    pub fn resolve(
+        &self,
+    ) -> Result<(Session, Origin), FetchError> {
+        if let Some(name) = self.profile {
+            return self
+                .profile(&self.load_config()?, name);
+        }
+
+        for var in SESSION_ENV {
+            if let Some(token) = (self.env)(var)
+                .filter(|t| !t.trim().is_empty())
+            {
+                return Ok((
+                    Session::new(token),
+                    Origin::Env(var),
+                ));
+            }
+        }
+
+        if let Some(path) = &self.dotenv
+            && let Some(token) = read_dotenv(path)?
+        {
+            return Ok((
+                Session::new(token),
+                Origin::DotEnv(path.clone()),
+            ));
+        }
+
+        let config = self.load_config()?;
+        if let Some(name) =
+            config.default_profile.as_deref()
+        {
+            return self.profile(&config, name);
+        }
+
+        Err(FetchError::MissingSession {
+            help: self.help(),
+        })
+    }
+
+    fn load_config(&self) -> Result<Config, FetchError> {
+        match &self.config {
+            Some(path) => Config::load(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn profile(
+        &self,
+        config: &Config,
+        name: &str,
+    ) -> Result<(Session, Origin), FetchError> {
+        let path = self.config.clone().unwrap_or_default();
+        match config.profiles.get(name) {
+            Some(profile) => Ok((
+                profile.session.clone(),
+                Origin::Profile {
+                    name: name.to_string(),
+                    config: path,
+                },
+            )),
+            None => Err(FetchError::UnknownProfile {
+                profile: name.to_string(),
+                help: if config.profiles.is_empty() {
+                    "There are no profiles yet; add a \
+                     `[profiles.<name>]` table with a \
+                     `session` key."
+                        .to_string()
+                } else {
+                    format!(
+                        "Known profiles: {}",
+                        config
+                            .profiles
+                            .keys()
+                            .map(|k| format!("`{k}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                },
+                path,
+            }),
+        }
+    }
+
+    fn help(&self) -> String {
+        let config = self
+            .config
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| {
+                "~/.config/aoc/config.toml".to_string()
+            });
+        format!(
+            "Copy the `session` cookie from adventofcode.com \
+             and either\n  \
+             - export it as `AOC_SESSION`,\n  \
+             - put `AOC_SESSION=...` in a `.env` file, or\n  \
+             - add it to a profile in {config}:\n\n    \
+             default_profile = \"personal\"\n\n    \
+             [profiles.personal]\n    \
+             session = \"...\""
+        )
+    }
+}
+
+fn find_dotenv() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(".env"))
+        .find(|path| path.is_file())
+}
+
+fn read_dotenv(
+    path: &Path,
+) -> Result<Option<String>, FetchError> {
+    let vars: Vec<(String, String)> =
+        dotenvy::from_path_iter(path)
+            .and_then(|iter| iter.collect())
+            .map_err(|e| {
+                std::io::Error::other(format!(
+                    "Failed to read {}: {e}",
+                    path.display()
+                ))
+            })?;
+    Ok(SESSION_ENV.iter().find_map(|var| {
+        vars.iter()
+            .find(|(k, v)| k == var && !v.trim().is_empty())
+            .map(|(_, v)| v.clone())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    const TOKEN: &str = "53616c7465645f5f0123456789abcdef";
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    fn with_config(contents: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("config.toml"), contents)
+            .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_session_is_redacted() {
+        let session = Session::new(TOKEN);
+        assert_eq!("********cdef", session.to_string());
+        assert_eq!(
+            "Session(********cdef)",
+            format!("{session:?}")
+        );
+        assert!(!format!("{session:?}").contains(TOKEN));
+        assert_eq!(
+            "********",
+            Session::new("short").to_string()
+        );
+    }
+
+    #[test]
+    fn test_env_wins_over_config() -> miette::Result<()> {
+        let dir = with_config(
+            "default_profile = \"a\"\n[profiles.a]\nsession = \"from-config\"\n",
+        );
+        let env = |var: &str| {
+            (var == "SESSION").then(|| TOKEN.to_string())
+        };
+        let lookup = Lookup {
+            profile: None,
+            env: &env,
+            dotenv: None,
+            config: Some(dir.path().join("config.toml")),
+        };
+        let (session, origin) = lookup.resolve()?;
+        assert_eq!(Session::new(TOKEN), session);
+        assert_eq!(Origin::Env("SESSION"), origin);
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_skips_broken_config() -> miette::Result<()>
+    {
+        let dir = with_config("default_profile = [\n");
+        let env = |var: &str| {
+            (var == "AOC_SESSION")
+                .then(|| TOKEN.to_string())
+        };
+        let lookup = Lookup {
+            profile: None,
+            env: &env,
+            dotenv: None,
+            config: Some(dir.path().join("config.toml")),
+        };
+        let (session, origin) = lookup.resolve()?;
+        assert_eq!(Session::new(TOKEN), session);
+        assert_eq!(Origin::Env("AOC_SESSION"), origin);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dotenv() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(
+            &path,
+            format!("OTHER=1\nAOC_SESSION={TOKEN}\n"),
+        )
+        .unwrap();
+        let lookup = Lookup {
+            profile: None,
+            env: &no_env,
+            dotenv: Some(path.clone()),
+            config: None,
+        };
+        let (session, origin) = lookup.resolve()?;
+        assert_eq!(Session::new(TOKEN), session);
+        assert_eq!(Origin::DotEnv(path), origin);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explicit_profile() -> miette::Result<()> {
+        let dir = with_config(
+            "default_profile = \"a\"\n\
+             [profiles.a]\nsession = \"aaa\"\n\
+             [profiles.b]\nsession = \"bbb\"\n",
+        );
+        let env = |_: &str| Some(TOKEN.to_string());
+        let lookup = Lookup {
+            profile: Some("b"),
+            env: &env,
+            dotenv: None,
+            config: Some(dir.path().join("config.toml")),
+        };
+        let (session, origin) = lookup.resolve()?;
+        assert_eq!(Session::new("bbb"), session);
+        assert!(matches!(
+            origin,
+            Origin::Profile { name, .. } if name == "b"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_profile() -> miette::Result<()> {
+        let dir = with_config(
+            "default_profile = \"a\"\n[profiles.a]\nsession = \" aaa\\n\"\n",
+        );
+        let lookup = Lookup {
+            profile: None,
+            env: &no_env,
+            dotenv: None,
+            config: Some(dir.path().join("config.toml")),
+        };
+        // Stray whitespace from the config file is
+        // trimmed off.
+        assert_eq!(
+            "session=aaa",
+            lookup.resolve()?.0.cookie()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let dir = with_config(
+            "[profiles.a]\nsession = \"aaa\"\n",
+        );
+        let lookup = Lookup {
+            profile: Some("c"),
+            env: &no_env,
+            dotenv: None,
+            config: Some(dir.path().join("config.toml")),
+        };
+        let err = lookup.resolve().unwrap_err();
+        let FetchError::UnknownProfile { help, .. } = err
+        else {
+            panic!("expected an unknown profile error");
+        };
+        assert_eq!("Known profiles: `a`", help);
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let lookup = Lookup {
+            profile: None,
+            env: &|var| {
+                (var == "AOC_SESSION")
+                    .then(|| "  ".to_string())
+            },
+            dotenv: None,
+            config: None,
+        };
+        let err = lookup.resolve().unwrap_err();
+        assert_eq!(
+            "No session token found",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::{fmt, time::Duration};
+
+use scraper::{Html, Selector};
+
+/// Which way a wrong numeric answer was off, when
+/// the site says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        /// How long until another answer is
+        /// accepted.
+        wait: Option<Duration>,
+    },
+    /// An answer was given too recently, so this
+    /// one wasn't checked at all.
+    TooSoon {
+        wait: Duration,
+    },
+    /// The part was already solved, or part one
+    /// isn't yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Verdict::Correct => {
+                write!(f, "That's the right answer!")
+            }
+            Verdict::Wrong { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => {
+                        write!(f, "; it's too high")?
+                    }
+                    Some(Hint::TooLow) => {
+                        write!(f, "; it's too low")?
+                    }
+                    None => {}
+                }
+                if let Some(wait) = wait {
+                    write!(
+                        f,
+                        " (wait {} before trying again)",
+                        format_wait(*wait)
+                    )?;
+                }
+                Ok(())
+            }
+            Verdict::TooSoon { wait } => write!(
+                f,
+                "An answer was given too recently; wait {} before trying again",
+                format_wait(*wait)
+            ),
+            Verdict::WrongLevel => write!(
+                f,
+                "That part isn't open for answers; is it already solved?"
+            ),
+        }
+    }
+}
+
+/// Reads the verdict off the page the site
+/// answers a submission with, or `None` if it
+/// doesn't look like one.
+pub fn parse(html: &str) -> Option<Verdict> {
+    let html = Html::parse_document(html);
+    let article = Selector::parse("main article")
+        .expect("valid selector");
+    let text: String = match html.select(&article).next() {
+        Some(article) => article.text().collect(),
+        None => html.root_element().text().collect(),
+    };
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if text.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        // "Please wait one minute", or "... on this
+        // puzzle, please wait 5 minutes".
+        let wait = text
+            .to_lowercase()
+            .split_once("please wait ")
+            .and_then(|(_, rest)| {
+                rest.split_once(" before trying again")
+            })
+            .and_then(|(wait, _)| parse_wait(wait));
+        Some(Verdict::Wrong { hint, wait })
+    } else if text
+        .contains("You gave an answer too recently")
+    {
+        let wait = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| {
+                rest.split_once(" left to wait")
+            })
+            .and_then(|(wait, _)| parse_wait(wait))
+            .unwrap_or(Duration::from_secs(60));
+        Some(Verdict::TooSoon { wait })
+    } else if text.contains(
+        "You don't seem to be solving the right level",
+    ) {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+/// Parses the site's ways of saying how long to
+/// wait: "one minute", "5 minutes", "2m 8s",
+/// "37s".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words: Vec<&str> =
+        text.split_whitespace().collect();
+    if let [amount, unit] = words.as_slice()
+        && unit.starts_with("minute")
+    {
+        let minutes = match *amount {
+            "one" | "a" => 1,
+            amount => amount.parse().ok()?,
+        };
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    let mut seconds = 0;
+    for word in &words {
+        let (amount, unit) =
+            [("h", 3600), ("m", 60), ("s", 1)]
+                .into_iter()
+                .find_map(|(suffix, unit)| {
+                    Some((word.strip_suffix(suffix)?, unit))
+                })?;
+        let amount: u64 = amount.parse().ok()?;
+        seconds += amount * unit;
+    }
+    (!words.is_empty())
+        .then(|| Duration::from_secs(seconds))
+}
+
+fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs();
+    match (seconds / 60, seconds % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main>\
+             <article><p>{message}</p></article>\
+             </main></body></html>"
+        )
+    }
+
+    #[rstest]
+    #[case(
+        "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer. \
+         <a href=\"/2024/day/5#part2\">[Continue to Part Two]</a>",
+        Verdict::Correct
+    )]
+    #[case(
+        "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+         you're using the full input data.  Please wait one minute before trying again. \
+         <a href=\"/2024/day/5\">[Return to Day 5]</a>",
+        Verdict::Wrong {
+            hint: Some(Hint::TooHigh),
+            wait: Some(Duration::from_secs(60)),
+        }
+    )]
+    #[case(
+        "That's not the right answer; your answer is too low.  Because you have guessed \
+         incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.",
+        Verdict::Wrong {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(300)),
+        }
+    )]
+    #[case(
+        "That's not the right answer.  If you're stuck, make sure you're using the full \
+         input data.",
+        Verdict::Wrong { hint: None, wait: None }
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer \
+         before trying again.  You have 2m 8s left to wait.",
+        Verdict::TooSoon { wait: Duration::from_secs(128) }
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer \
+         before trying again.  You have 37s left to wait.",
+        Verdict::TooSoon { wait: Duration::from_secs(37) }
+    )]
+    #[case(
+        "You don't seem to be solving the right level.  Did you already complete it? \
+         <a href=\"/2024/day/5\">[Return to Day 5]</a>",
+        Verdict::WrongLevel
+    )]
+    fn test_parse(
+        #[case] message: &str,
+        #[case] expected: Verdict,
+    ) {
+        assert_eq!(Some(expected), parse(&page(message)));
+    }
+
+    #[test]
+    fn test_parse_unknown_page() {
+        assert_eq!(None, parse(&page("Hello")));
+    }
+
+    #[rstest]
+    #[case("one minute", Some(60))]
+    #[case("10 minutes", Some(600))]
+    #[case("1h 2m 3s", Some(3723))]
+    #[case("37s", Some(37))]
+    #[case("a while", None)]
+    #[case("", None)]
+    #[case("5é", None)]
+    #[case("1m 30秒", None)]
+    fn test_parse_wait(
+        #[case] text: &str,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(
+            expected.map(Duration::from_secs),
+            parse_wait(text)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "That's not the right answer; it's too low (wait 1m before trying again)",
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+                wait: Some(Duration::from_secs(60)),
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "An answer was given too recently; wait 2m 8s before trying again",
+            Verdict::TooSoon {
+                wait: Duration::from_secs(128)
+            }
+            .to_string()
+        );
+    }
+}
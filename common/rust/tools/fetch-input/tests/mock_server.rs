@@ -0,0 +1,549 @@
+//! Runs the client against an in-process mock of
+//! adventofcode.com.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use aoc_core::Part;
+use fetch_input::{
+    Client, FetchError,
+    bulk::{self, Status},
+    http::{Http, Settings},
+    leaderboard::{self, MIN_REFRESH},
+    session::Session,
+    submit::{Hint, Verdict},
+};
+use mockito::{Matcher, Server, ServerGuard};
+use tempfile::TempDir;
+
+const TOKEN: &str = "53616c7465645f5f0123456789abcdef";
+const INPUT: &str = "47|53\n97|13\n\n75,47,61,53,29\n";
+
+/// Retries straight away and without throttling,
+/// so tests don't have to wait.
+fn client(server: &ServerGuard) -> Client {
+    let http = Http::new(Settings {
+        contact: Some("me@example.com".to_string()),
+        min_interval: Duration::ZERO,
+        state: None,
+        backoff: Duration::ZERO,
+        max_wait: Duration::ZERO,
+        ..Settings::default()
+    });
+    Client::with_http(
+        server.url(),
+        Session::new(TOKEN),
+        http,
+    )
+}
+
+/// Inputs directory already holding a good input
+/// for 2024 day 5.
+fn cached_input() -> (TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = aoc_core::input::path(dir.path(), 2024, 5);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, INPUT).unwrap();
+    (dir, path)
+}
+
+fn assert_untouched(path: &Path) {
+    assert_eq!(INPUT, fs::read_to_string(path).unwrap());
+    assert!(!path.with_extension("txt.partial").exists());
+}
+
+#[test]
+fn test_success() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/2024/day/5/input")
+        .match_header(
+            "cookie",
+            format!("session={TOKEN}").as_str(),
+        )
+        .with_body(INPUT)
+        .create();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = aoc_core::input::path(dir.path(), 2024, 5);
+    client(&server).download_input(2024, 5, &path).unwrap();
+
+    mock.assert();
+    assert_eq!(
+        INPUT,
+        fs::read_to_string(&path).unwrap()
+    );
+}
+
+#[test]
+fn test_user_agent() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/2024/day/5/input")
+        .match_header(
+            "user-agent",
+            Matcher::Regex(
+                r"^fetch-input/\S+ \(me@example.com\)$"
+                    .to_string(),
+            ),
+        )
+        .with_body(INPUT)
+        .create();
+
+    client(&server).input(2024, 5).unwrap();
+    mock.assert();
+}
+
+#[test]
+fn test_retries_server_errors() {
+    let mut server = Server::new();
+    let unavailable = server
+        .mock("GET", "/2024/day/5/input")
+        .with_status(503)
+        .expect(2)
+        .create();
+    let available = server
+        .mock("GET", "/2024/day/5/input")
+        .with_body(INPUT)
+        .create();
+
+    assert_eq!(
+        INPUT,
+        client(&server).input(2024, 5).unwrap()
+    );
+    unavailable.assert();
+    available.assert();
+}
+
+#[test]
+fn test_gives_up_after_retries() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/2024/day/5/input")
+        .with_status(502)
+        .expect(4)
+        .create();
+
+    let err = client(&server).input(2024, 5).unwrap_err();
+    assert!(matches!(
+        err,
+        FetchError::UnexpectedResponse { status: 502, .. }
+    ));
+    mock.assert();
+}
+
+#[test]
+fn test_bad_cookie() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/2024/day/5/input")
+        .with_status(400)
+        .with_body(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+        .create();
+
+    let (_dir, path) = cached_input();
+    let err = client(&server)
+        .download_input(2024, 5, &path)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::ExpiredSession
+    ));
+    assert_untouched(&path);
+}
+
+#[test]
+fn test_locked_day() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/2024/day/5/input")
+        .with_status(404)
+        .with_body(
+            "Please don't repeatedly request this endpoint before it unlocks! \
+             The calendar countdown is synchronized with the server time; \
+             the link will be enabled on the calendar the instant this \
+             puzzle becomes available.\n",
+        )
+        .create();
+
+    let (_dir, path) = cached_input();
+    let err = client(&server)
+        .download_input(2024, 5, &path)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::NotUnlocked { year: 2024, day: 5 }
+    ));
+    assert_untouched(&path);
+}
+
+#[test]
+fn test_rate_limited() {
+    let mut server = Server::new();
+    server
+        .mock("GET", Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "60")
+        .create();
+
+    let (_dir, path) = cached_input();
+    let err = client(&server)
+        .download_input(2024, 5, &path)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::RateLimited { .. }
+    ));
+    assert_untouched(&path);
+}
+
+#[test]
+fn test_partial_write() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/2024/day/5/input")
+        .with_chunked_body(|w| {
+            w.write_all(b"47|53\n97|")?;
+            Err(std::io::Error::other(
+                "connection reset",
+            ))
+        })
+        .create();
+
+    let (_dir, path) = cached_input();
+    let err = client(&server)
+        .download_input(2024, 5, &path)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::Request { .. }
+    ));
+    assert_untouched(&path);
+}
+
+#[test]
+fn test_fetch_days() {
+    let mut server = Server::new();
+    let fetched = server
+        .mock("GET", "/2024/day/6/input")
+        .with_body(INPUT)
+        .create();
+    let missing = server
+        .mock("GET", "/2024/day/7/input")
+        .with_status(404)
+        .with_body("404 Not Found\n")
+        .create();
+    let locked = server
+        .mock("GET", "/2024/day/8/input")
+        .with_status(404)
+        .with_body(
+            "Please don't repeatedly request this endpoint before it unlocks!\n",
+        )
+        .create();
+    let after_locked = server
+        .mock("GET", "/2024/day/9/input")
+        .expect(0)
+        .create();
+
+    let (dir, _) = cached_input();
+    let mut progress = vec![];
+    let statuses = bulk::fetch_days(
+        &client(&server),
+        dir.path(),
+        2024,
+        5..=9,
+        false,
+        |day, _| progress.push(day),
+    );
+
+    fetched.assert();
+    missing.assert();
+    locked.assert();
+    after_locked.assert();
+    assert_eq!(vec![5, 6, 7, 8, 9], progress);
+    assert_eq!(
+        vec![
+            (5, Status::Cached),
+            (6, Status::Fetched),
+            (
+                7,
+                Status::Failed {
+                    reason: format!(
+                        "There is no puzzle at {}/2024/day/7/input",
+                        server.url()
+                    )
+                }
+            ),
+            (8, Status::Locked),
+            (9, Status::Locked),
+        ],
+        statuses
+    );
+    assert_eq!(
+        INPUT,
+        fs::read_to_string(aoc_core::input::path(
+            dir.path(),
+            2024,
+            6
+        ))
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fetch_days_past_the_calendar() {
+    let mut server = Server::new();
+    let fetched = server
+        .mock("GET", "/2025/day/11/input")
+        .with_body(INPUT)
+        .create();
+    let locked = server
+        .mock("GET", "/2025/day/12/input")
+        .with_status(404)
+        .with_body(
+            "Please don't repeatedly request this endpoint before it unlocks!\n",
+        )
+        .create();
+    // Not even a locked day makes day 13 exist.
+    let past = server
+        .mock("GET", "/2025/day/13/input")
+        .expect(0)
+        .create();
+
+    let dir = tempfile::tempdir().unwrap();
+    let statuses = bulk::fetch_days(
+        &client(&server),
+        dir.path(),
+        2025,
+        11..=13,
+        false,
+        |_, _| {},
+    );
+
+    fetched.assert();
+    locked.assert();
+    past.assert();
+    assert_eq!(
+        vec![
+            (11, Status::Fetched),
+            (12, Status::Locked),
+            (13, Status::NoSuchDay)
+        ],
+        statuses
+    );
+    assert_eq!(
+        "1 fetched, 0 cached, 1 locked, 1 no such day, 0 failed",
+        bulk::summary(&statuses)
+    );
+}
+
+#[test]
+fn test_fetch_days_stops_on_expired_session() {
+    let mut server = Server::new();
+    let rejected = server
+        .mock("GET", "/2024/day/1/input")
+        .with_status(400)
+        .create();
+    let skipped = server
+        .mock("GET", "/2024/day/2/input")
+        .expect(0)
+        .create();
+
+    let dir = tempfile::tempdir().unwrap();
+    let statuses = bulk::fetch_days(
+        &client(&server),
+        dir.path(),
+        2024,
+        1..=2,
+        false,
+        |_, _| {},
+    );
+
+    rejected.assert();
+    skipped.assert();
+    assert!(
+        statuses.iter().all(|(_, s)| matches!(
+            s,
+            Status::Failed { .. }
+        ))
+    );
+    assert_eq!(
+        "0 fetched, 0 cached, 0 locked, 2 failed",
+        bulk::summary(&statuses)
+    );
+}
+
+#[test]
+fn test_leaderboard_is_cached() {
+    let mut server = Server::new();
+    let mock = server
+        .mock(
+            "GET",
+            "/2024/leaderboard/private/view/101.json",
+        )
+        .match_header(
+            "cookie",
+            format!("session={TOKEN}").as_str(),
+        )
+        .with_body(include_str!(
+            "fixtures/leaderboard.json"
+        ))
+        .expect(2)
+        .create();
+
+    let client = client(&server);
+    let cache = tempfile::tempdir().unwrap();
+    let load = |now| {
+        leaderboard::load(
+            &client,
+            2024,
+            101,
+            cache.path(),
+            now,
+        )
+        .unwrap()
+    };
+
+    let first = load(SystemTime::now());
+    assert_eq!(None, first.previous);
+    assert!(
+        leaderboard::cache_path(cache.path(), 2024, 101)
+            .exists()
+    );
+
+    // Within the refresh interval the cached copy
+    // is used, so only two requests are made.
+    let cached = load(SystemTime::now());
+    assert_eq!(first.leaderboard, cached.leaderboard);
+
+    let refreshed = load(
+        SystemTime::now()
+            + MIN_REFRESH
+            + Duration::from_secs(1),
+    );
+    assert_eq!(
+        Some(first.leaderboard),
+        refreshed.previous
+    );
+    mock.assert();
+}
+
+#[test]
+fn test_leaderboard_not_a_member() {
+    let mut server = Server::new();
+    // The site sends anyone who can't see a
+    // leaderboard back to the list of them.
+    server
+        .mock("GET", "/2024/leaderboard/private/view/101.json")
+        .with_body("<!DOCTYPE html><html><body>Private Leaderboard</body></html>")
+        .create();
+
+    let cache = tempfile::tempdir().unwrap();
+    let err = leaderboard::load(
+        &client(&server),
+        2024,
+        101,
+        cache.path(),
+        SystemTime::now(),
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::InvalidLeaderboard { .. }
+    ));
+    assert!(
+        !leaderboard::cache_path(cache.path(), 2024, 101)
+            .exists()
+    );
+}
+
+#[test]
+fn test_puzzle_page() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/2024/day/5")
+        .match_header("cookie", format!("session={TOKEN}").as_str())
+        .with_body(
+            "<!DOCTYPE html><html><body><main>\
+             <article class=\"day-desc\"><h2>--- Day 5: Print Queue ---</h2>\
+             <p>Safety protocols <em>clearly</em> indicate...</p></article>\
+             </main></body></html>",
+        )
+        .create();
+
+    let client = client(&server);
+    let html = client.puzzle_page(2024, 5).unwrap();
+    let articles = fetch_input::puzzle::articles(
+        &html,
+        client.base_url(),
+    );
+
+    assert_eq!(1, articles.len());
+    assert_eq!("Day 5: Print Queue", articles[0].title);
+    assert_eq!(
+        "Safety protocols **clearly** indicate...",
+        articles[0].body
+    );
+}
+
+#[test]
+fn test_submit() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/2024/day/5/answer")
+        .match_header("cookie", format!("session={TOKEN}").as_str())
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("level".into(), "2".into()),
+            Matcher::UrlEncoded("answer".into(), "4321".into()),
+        ]))
+        .with_body(
+            "<!DOCTYPE html><html><body><main><article>\
+             <p>That's not the right answer; your answer is too low.  \
+             Please wait one minute before trying again.</p>\
+             </article></main></body></html>",
+        )
+        .create();
+
+    let verdict = client(&server)
+        .submit(2024, 5, Part::Two, "4321")
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(
+        Verdict::Wrong {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(60)),
+        },
+        verdict
+    );
+}
+
+#[test]
+fn test_submit_unknown_response() {
+    let mut server = Server::new();
+    server
+        .mock("POST", "/2024/day/5/answer")
+        .with_body(
+            "<html><body>Something else</body></html>",
+        )
+        .create();
+
+    let err = client(&server)
+        .submit(2024, 5, Part::One, "143")
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        FetchError::UnknownVerdict { .. }
+    ));
+}